@@ -0,0 +1,8 @@
+#!/usr/bin/env bash
+#
+# example decision script. anything printed before the last line ends up in
+# the juror's logs; the last line is the winning choice, by index or by text.
+
+echo "checking the final score..." >&2
+echo "home 2, away 1"
+echo "home"
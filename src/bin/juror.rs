@@ -0,0 +1,377 @@
+//! Runs a trial's decision script on behalf of a juror and prints the
+//! `ExecuteMsg` with which to submit the outcome.
+//!
+//! Usage: juror --trial <FILE|-> --script <FILE> [--timeout <SECONDS>]
+//!              [--logs-uri <URI>]
+//!
+//! The trial is given as the JSON returned by the `get_ballot` query, read from
+//! a file or from stdin. The script is read from a local file, fetched from the
+//! verdict's script URI or returned by the `get_script` query, and must match
+//! the verdict's script hash. It runs in a subprocess with a cleared
+//! environment, inside a scratch directory, and is killed if it runs longer
+//! than the timeout. On unix, rlimits also cap its CPU time, file sizes and
+//! open files. The script is NOT sandboxed: it can still read and write any
+//! file the juror can and reach the network, so untrusted scripts should only
+//! be run inside a container or VM. The last non-empty line printed by the
+//! script is parsed as its output, according to the verdict's runtime, where
+//! "invalid" rules the trial invalid. Logs larger than the trial allows inline
+//! must be uploaded elsewhere, and referenced by `--logs-uri`.
+
+use cosmwasm_std::{from_slice, to_vec, Decimal};
+use cw_contract_template::{
+  models::{Allocation, Logs, OutputFormat, ProgrammingLanguage, Trial},
+  msg::{ExecuteMsg, GetTrialResponse},
+  state::DEFAULT_MAX_INLINE_LOGS,
+};
+use sha2::{Digest, Sha256};
+use std::{
+  env, fs,
+  io::{self, Read},
+  path::{Path, PathBuf},
+  process::{self, Command, Stdio},
+  str::FromStr,
+  thread,
+  time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+const DEFAULT_TIMEOUT_SECONDS: u64 = 60;
+/// The largest file a script may write.
+const MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+const MAX_OPEN_FILES: u64 = 256;
+/// The output with which a script rules the trial invalid, unless a choice
+/// has that text.
+const INVALID_OUTPUT: &str = "invalid";
+
+struct Args {
+  trial: String,
+  script: String,
+  timeout: Duration,
+  logs_uri: Option<String>,
+}
+
+/// The captured result of running a script.
+struct Run {
+  success: bool,
+  stdout: String,
+  stderr: String,
+}
+
+fn main() {
+  match parse_args(env::args().skip(1).collect()).and_then(|args| run(&args)) {
+    Ok(msg) => println!("{}", msg),
+    Err(err) => {
+      eprintln!("juror: {}", err);
+      process::exit(1);
+    },
+  }
+}
+
+fn parse_args(args: Vec<String>) -> Result<Args, String> {
+  let mut trial = None;
+  let mut script = None;
+  let mut timeout = Duration::from_secs(DEFAULT_TIMEOUT_SECONDS);
+  let mut logs_uri = None;
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+    match arg.as_str() {
+      "--trial" => trial = Some(value()?),
+      "--script" => script = Some(value()?),
+      "--logs-uri" => logs_uri = Some(value()?),
+      "--timeout" => {
+        let seconds = value()?;
+        timeout = Duration::from_secs(
+          seconds
+            .parse()
+            .map_err(|_| format!("invalid timeout: {}", seconds))?,
+        );
+      },
+      _ => return Err(format!("unexpected argument: {}", arg)),
+    }
+  }
+  Ok(Args {
+    trial: trial.ok_or("missing --trial")?,
+    script: script.ok_or("missing --script")?,
+    timeout,
+    logs_uri,
+  })
+}
+
+fn run(args: &Args) -> Result<String, String> {
+  let trial = read_trial(&args.trial)?;
+  let script =
+    fs::read_to_string(&args.script).map_err(|err| format!("{}: {}", args.script, err))?;
+
+  // refuse to run a script other than the one the trial is decided by
+  let script_hash = format!("{:x}", Sha256::digest(script.as_bytes()));
+  if script_hash != trial.verdict.script.hash {
+    return Err(format!(
+      "script hash {} does not match the verdict's {}",
+      script_hash, trial.verdict.script.hash
+    ));
+  }
+
+  let run = execute(&trial, &script, args.timeout)?;
+  if !run.success {
+    return Err(format!("script failed:\n{}", run.stderr));
+  }
+  let output_logs = if run.stderr.is_empty() {
+    run.stdout.clone()
+  } else {
+    format!("{}{}", run.stdout, run.stderr)
+  };
+  let max_inline_logs = trial
+    .verdict
+    .max_inline_logs
+    .unwrap_or(DEFAULT_MAX_INLINE_LOGS) as usize;
+  let is_inline = output_logs.len() <= max_inline_logs;
+  if !is_inline && args.logs_uri.is_none() {
+    return Err(format!(
+      "logs exceed {} bytes and must be uploaded, then given by --logs-uri",
+      max_inline_logs
+    ));
+  }
+  let logs = Logs {
+    hash: format!("{:x}", Sha256::digest(output_logs.as_bytes())),
+    len: output_logs.len() as u64,
+    uri: args.logs_uri.clone(),
+    inline: if is_inline { Some(output_logs) } else { None },
+  };
+
+  let output = run
+    .stdout
+    .lines()
+    .map(str::trim)
+    .rev()
+    .find(|line| !line.is_empty())
+    .ok_or("script printed no output")?;
+  let msg = decide_msg(&trial, output, logs, script_hash)?;
+  let json = to_vec(&msg).map_err(|err| err.to_string())?;
+  String::from_utf8(json).map_err(|err| err.to_string())
+}
+
+fn read_trial(path: &str) -> Result<Trial, String> {
+  let mut json = vec![];
+  if path == "-" {
+    io::stdin()
+      .read_to_end(&mut json)
+      .map_err(|err| err.to_string())?;
+  } else {
+    json = fs::read(path).map_err(|err| format!("{}: {}", path, err))?;
+  }
+  let resp: GetTrialResponse =
+    from_slice(&json).map_err(|err| format!("invalid trial: {}", err))?;
+  Ok(resp.trial)
+}
+
+/// The command that runs a script for the given language, along with the
+/// extension its source file needs.
+fn interpreter(
+  language: &ProgrammingLanguage
+) -> (&'static str, &'static [&'static str], &'static str) {
+  match language {
+    ProgrammingLanguage::Bash => ("bash", &[], "sh"),
+    ProgrammingLanguage::Python => ("python3", &[], "py"),
+    ProgrammingLanguage::TypeScript => ("ts-node", &[], "ts"),
+    ProgrammingLanguage::Rust => ("rust-script", &[], "rs"),
+    ProgrammingLanguage::Go => ("go", &["run"], "go"),
+    ProgrammingLanguage::Wasm => ("wasmtime", &[], "wat"),
+  }
+}
+
+/// Run the script in a scratch directory with a cleared environment, apart
+/// from `PATH` and the variables pinned by the runtime, with capped resources,
+/// killing it once the timeout elapses.
+fn execute(
+  trial: &Trial,
+  script: &str,
+  timeout: Duration,
+) -> Result<Run, String> {
+  let (program, program_args, extension) = interpreter(&trial.verdict.language);
+  let dir = scratch_dir()?;
+  let path = dir.join(format!("script.{}", extension));
+  fs::write(&path, script).map_err(|err| err.to_string())?;
+
+  let mut command = Command::new(program);
+  command
+    .args(program_args)
+    .arg(&path)
+    .current_dir(&dir)
+    .env_clear()
+    .env("PATH", env::var("PATH").unwrap_or_default())
+    .env("HOME", &dir)
+    .stdin(Stdio::null())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped());
+  if let Some(runtime) = &trial.verdict.runtime {
+    command.args(&runtime.args);
+    for var in runtime.env.iter() {
+      command.env(&var.name, &var.value);
+    }
+  }
+  #[cfg(unix)]
+  limit_resources(&mut command, timeout);
+
+  let result = wait(command, timeout);
+  let _ = fs::remove_dir_all(&dir);
+  result
+}
+
+/// Lower the script's rlimits before it runs: CPU time to the timeout, file
+/// sizes to `MAX_FILE_BYTES`, open files to `MAX_OPEN_FILES` and core dumps to
+/// none. Limits already lower than these are kept.
+#[cfg(unix)]
+fn limit_resources(
+  command: &mut Command,
+  timeout: Duration,
+) {
+  use std::os::unix::process::CommandExt;
+
+  let limits = [
+    (libc::RLIMIT_CPU, timeout.as_secs().max(1)),
+    (libc::RLIMIT_FSIZE, MAX_FILE_BYTES),
+    (libc::RLIMIT_NOFILE, MAX_OPEN_FILES),
+    (libc::RLIMIT_CORE, 0),
+  ];
+  // safe, since only getrlimit and setrlimit, which are async-signal-safe, are
+  // called between fork and exec
+  unsafe {
+    command.pre_exec(move || {
+      for (resource, max) in limits.iter() {
+        let mut limit = libc::rlimit {
+          rlim_cur: 0,
+          rlim_max: 0,
+        };
+        if libc::getrlimit(*resource, &mut limit) != 0 {
+          return Err(io::Error::last_os_error());
+        }
+        limit.rlim_max = limit.rlim_max.min(*max as libc::rlim_t);
+        limit.rlim_cur = limit.rlim_cur.min(limit.rlim_max);
+        if libc::setrlimit(*resource, &limit) != 0 {
+          return Err(io::Error::last_os_error());
+        }
+      }
+      Ok(())
+    });
+  }
+}
+
+fn wait(
+  mut command: Command,
+  timeout: Duration,
+) -> Result<Run, String> {
+  let mut child = command
+    .spawn()
+    .map_err(|err| format!("failed to run {:?}: {}", command.get_program(), err))?;
+
+  // drain the pipes as the script runs, so that it can't block on a full pipe
+  let mut stdout = child.stdout.take().ok_or("no stdout")?;
+  let mut stderr = child.stderr.take().ok_or("no stderr")?;
+  let stdout_reader = thread::spawn(move || {
+    let mut buf = String::new();
+    stdout.read_to_string(&mut buf).map(|_| buf)
+  });
+  let stderr_reader = thread::spawn(move || {
+    let mut buf = String::new();
+    stderr.read_to_string(&mut buf).map(|_| buf)
+  });
+
+  let started = Instant::now();
+  let status = loop {
+    if let Some(status) = child.try_wait().map_err(|err| err.to_string())? {
+      break status;
+    }
+    if started.elapsed() >= timeout {
+      let _ = child.kill();
+      let _ = child.wait();
+      return Err(format!("script timed out after {}s", timeout.as_secs()));
+    }
+    thread::sleep(Duration::from_millis(20));
+  };
+
+  Ok(Run {
+    success: status.success(),
+    stdout: join(stdout_reader)?,
+    stderr: join(stderr_reader)?,
+  })
+}
+
+fn join(reader: thread::JoinHandle<io::Result<String>>) -> Result<String, String> {
+  reader
+    .join()
+    .map_err(|_| "failed to read output".to_owned())?
+    .map_err(|err| err.to_string())
+}
+
+fn scratch_dir() -> Result<PathBuf, String> {
+  let nanos = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map_err(|err| err.to_string())?
+    .as_nanos();
+  let dir = Path::new(&env::temp_dir()).join(format!("verdict-juror-{}-{}", process::id(), nanos));
+  fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+  Ok(dir)
+}
+
+/// Parse the script's output into the message that submits it. Without a
+/// runtime, the output is a value in scalar trials, a ranking in ranked trials
+/// and a choice otherwise. Whatever the format, "invalid" rules the trial
+/// invalid, unless it's the text of a choice.
+fn decide_msg(
+  trial: &Trial,
+  output: &str,
+  logs: Logs,
+  script_hash: String,
+) -> Result<ExecuteMsg, String> {
+  let format = match &trial.verdict.runtime {
+    Some(runtime) => runtime.output.clone(),
+    None if trial.verdict.scalar.is_some() => OutputFormat::Value,
+    None if trial.verdict.payout_table.is_some() => OutputFormat::Ranking,
+    None => OutputFormat::Choice,
+  };
+  let invalid = |err: String| format!("invalid output {:?}: {}", output, err);
+  // in choice trials, a choice could itself be named "invalid"
+  let is_choice = format == OutputFormat::Choice
+    && trial
+      .choices
+      .iter()
+      .any(|choice| choice.text == INVALID_OUTPUT);
+  if output == INVALID_OUTPUT && !is_choice {
+    return Ok(ExecuteMsg::DecideInvalid { logs, script_hash });
+  }
+  Ok(match format {
+    // a choice is given by index or by its exact text
+    OutputFormat::Choice => ExecuteMsg::Decide {
+      choice: output
+        .parse()
+        .ok()
+        .or_else(|| {
+          trial
+            .choices
+            .iter()
+            .position(|choice| choice.text == output)
+            .map(|i| i as u32)
+        })
+        .filter(|choice| (*choice as usize) < trial.choices.len())
+        .ok_or_else(|| invalid("not a choice".to_owned()))?,
+      logs,
+      script_hash,
+    },
+    OutputFormat::Value => ExecuteMsg::DecideValue {
+      value: Decimal::from_str(output).map_err(|err| invalid(err.to_string()))?,
+      logs,
+      script_hash,
+    },
+    OutputFormat::Allocations => ExecuteMsg::DecideSplit {
+      allocations: from_slice::<Vec<Allocation>>(output.as_bytes())
+        .map_err(|err| invalid(err.to_string()))?,
+      logs,
+      script_hash,
+    },
+    OutputFormat::Ranking => ExecuteMsg::DecideRanking {
+      ranking: from_slice::<Vec<u32>>(output.as_bytes()).map_err(|err| invalid(err.to_string()))?,
+      logs,
+      script_hash,
+    },
+  })
+}
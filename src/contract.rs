@@ -1,11 +1,14 @@
 #[cfg(not(feature = "library"))]
 use crate::error::ContractError;
+use crate::events;
 use crate::execute;
 use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
 use crate::query;
 use crate::state;
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+  to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsgResult,
+};
 use cw2::set_contract_version;
 
 const CONTRACT_NAME: &str = "crates.io:cw-contract-template";
@@ -32,20 +35,128 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
   match msg {
     ExecuteMsg::Vote { choice, weight } => execute::vote(deps, env, info, choice as usize, weight),
-    ExecuteMsg::Decide { choice, logs } => execute::decide(deps, env, info, choice as usize, &logs),
+    ExecuteMsg::Decide {
+      choice,
+      logs,
+      script_hash,
+    } => execute::decide(deps, env, info, choice as usize, logs, &script_hash),
+    ExecuteMsg::DecideValue {
+      value,
+      logs,
+      script_hash,
+    } => execute::decide_value(deps, env, info, value, logs, &script_hash),
+    ExecuteMsg::DecideInvalid { logs, script_hash } => {
+      execute::decide_invalid(deps, env, info, logs, &script_hash)
+    },
+    ExecuteMsg::DecideSplit {
+      allocations,
+      logs,
+      script_hash,
+    } => execute::decide_split(deps, env, info, allocations, logs, &script_hash),
+    ExecuteMsg::DecideRanking {
+      ranking,
+      logs,
+      script_hash,
+    } => execute::decide_ranking(deps, env, info, ranking, logs, &script_hash),
     ExecuteMsg::Claim {} => execute::claim(deps, env, info),
     ExecuteMsg::Cancel { reason } => execute::cancel(deps, env, info, &reason),
+    ExecuteMsg::CosignCancel {} => execute::cosign_cancel(deps, env, info),
+    ExecuteMsg::Resolve {} => execute::resolve(deps, env, info),
+    ExecuteMsg::SubmitAttestations { attestations } => {
+      execute::submit_attestations(deps, env, info, attestations)
+    },
+    ExecuteMsg::SubmitSignedResult { payload, signature } => {
+      execute::submit_signed_result(deps, env, info, payload, signature)
+    },
+    ExecuteMsg::SubmitBeacon {
+      round,
+      signature,
+      previous_signature,
+    } => execute::submit_beacon(deps, env, info, round, signature, previous_signature),
+    ExecuteMsg::AddHook { addr } => execute::add_hook(deps, env, info, &addr),
+    ExecuteMsg::RemoveHook { addr } => execute::remove_hook(deps, env, info, &addr),
+    ExecuteMsg::UpdateTrial {
+      prompt,
+      choice_texts,
+      style,
+    } => execute::update_trial(deps, env, info, prompt, choice_texts, style),
+    ExecuteMsg::ConfigureChoices { choices } => execute::configure_choices(deps, info, choices),
+    ExecuteMsg::ConfigureJury { jury, jury_pool } => {
+      execute::configure_jury(deps, info, jury, jury_pool)
+    },
+    ExecuteMsg::ConfigureCancelPolicy { cancel_policy } => {
+      execute::configure_cancel_policy(deps, info, cancel_policy)
+    },
+    ExecuteMsg::ConfigureVerdict { verdict } => execute::configure_verdict(deps, info, *verdict),
+    ExecuteMsg::AppendScript { chunk } => execute::append_script(deps, info, &chunk),
+    ExecuteMsg::Publish {} => execute::publish(deps, env, info),
+    ExecuteMsg::ProposeOwner { new_owner, expiry } => {
+      execute::propose_owner(deps, env, info, &new_owner, expiry)
+    },
+    ExecuteMsg::AcceptOwnership {} => execute::accept_ownership(deps, env, info),
+    ExecuteMsg::RenounceOwnership {} => execute::renounce_ownership(deps, env, info),
+    ExecuteMsg::SetGuardian { guardian } => execute::set_guardian(deps, env, info, guardian),
+    ExecuteMsg::Pause { operation, seconds } => execute::pause(deps, env, info, operation, seconds),
+    ExecuteMsg::Unpause { operation } => execute::unpause(deps, env, info, operation),
   }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
   deps: Deps,
-  _env: Env,
+  env: Env,
   msg: QueryMsg,
 ) -> StdResult<Binary> {
   let result = match msg {
     QueryMsg::GetBallot {} => to_binary(&query::get_trial(deps)?),
+    QueryMsg::GetCancellation {} => to_binary(&query::get_cancellation(deps)?),
+    QueryMsg::GetStats {} => to_binary(&query::get_stats(deps)?),
+    QueryMsg::GetLeaderboard { choice, limit } => {
+      to_binary(&query::get_leaderboard(deps, choice, limit)?)
+    },
+    QueryMsg::GetOddsHistory { start_after, limit } => {
+      to_binary(&query::get_odds_history(deps, start_after, limit)?)
+    },
+    QueryMsg::GetHistory { start_after, limit } => {
+      to_binary(&query::get_history(deps, start_after, limit)?)
+    },
+    QueryMsg::GetOdds {} => to_binary(&query::get_odds(deps)?),
+    QueryMsg::GetJuryPool {} => to_binary(&query::get_jury_pool(deps)?),
+    QueryMsg::GetScript {} => to_binary(&query::get_script(deps)?),
+    QueryMsg::GetJurorLogs { juror } => to_binary(&query::get_juror_logs(deps, juror)?),
+    QueryMsg::GetResolution {} => to_binary(&query::get_resolution(deps)?),
+    QueryMsg::GetHooks {} => to_binary(&query::get_hooks(deps)?),
+    QueryMsg::GetConfigHash {} => to_binary(&query::get_config_hash(deps)?),
+    QueryMsg::GetOwnership {} => to_binary(&query::get_ownership(deps)?),
+    QueryMsg::GetPauseState {} => to_binary(&query::get_pause_state(deps, env)?),
   }?;
   Ok(result)
 }
+
+/// Hook msgs are sent with `reply_on_error`, so this is only reached when a hook
+/// fails. The failure is reported, rather than propagated, so that a failing
+/// hook can't block the transition that triggered it.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+  deps: DepsMut,
+  _env: Env,
+  msg: Reply,
+) -> Result<Response, ContractError> {
+  let hook = state::HOOKS
+    .load(deps.storage)?
+    .get(msg.id as usize)
+    .map_or_else(String::new, |hook| hook.to_string());
+  let error = match msg.result {
+    SubMsgResult::Err(error) => error,
+    SubMsgResult::Ok(_) => String::new(),
+  };
+  Ok(
+    Response::new()
+      .add_event(events::hook_failed(&hook, &error))
+      .add_attributes(vec![
+        ("action", "hook_failed".to_owned()),
+        ("hook", hook),
+        ("error", error),
+      ]),
+  )
+}
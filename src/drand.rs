@@ -0,0 +1,70 @@
+use bls12_381::{
+  hash_to_curve::{ExpandMsgXmd, HashToCurve},
+  pairing, G1Affine, G2Affine, G2Projective,
+};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+
+/// The domain separation tag used by drand's chained scheme.
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Verify a beacon from a drand network running the chained scheme, where the
+/// signature of each round covers the previous round's signature.
+pub fn verify(
+  pubkey: &[u8],
+  round: u64,
+  previous_signature: &[u8],
+  signature: &[u8],
+) -> bool {
+  let pubkey = match <[u8; 48]>::try_from(pubkey)
+    .ok()
+    .and_then(|bytes| Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)))
+  {
+    Some(pubkey) => pubkey,
+    None => return false,
+  };
+  let signature = match <[u8; 96]>::try_from(signature)
+    .ok()
+    .and_then(|bytes| Option::<G2Affine>::from(G2Affine::from_compressed(&bytes)))
+  {
+    Some(signature) => signature,
+    None => return false,
+  };
+
+  let mut hasher = Sha256::new();
+  hasher.update(previous_signature);
+  hasher.update(round.to_be_bytes());
+  let message = hasher.finalize();
+
+  let hashed: G2Affine =
+    <G2Projective as HashToCurve<ExpandMsgXmd<sha2_v9::Sha256>>>::hash_to_curve(message, DST)
+      .into();
+  pairing(&G1Affine::generator(), &signature) == pairing(&pubkey, &hashed)
+}
+
+/// The randomness of a beacon, i.e. the sha256 digest of its signature.
+pub fn randomness(signature: &[u8]) -> Vec<u8> {
+  Sha256::digest(signature).to_vec()
+}
+
+/// Draw `k` distinct indices out of `n` through a Fisher-Yates shuffle seeded
+/// by the given randomness, so that anyone can reproduce the draw.
+pub fn draw(
+  randomness: &[u8],
+  n: usize,
+  k: usize,
+) -> Vec<usize> {
+  let mut indices: Vec<usize> = (0..n).collect();
+  for i in 0..k.min(n) {
+    let mut hasher = Sha256::new();
+    hasher.update(randomness);
+    hasher.update((i as u64).to_be_bytes());
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let j = i + (u64::from_be_bytes(bytes) % (n - i) as u64) as usize;
+    indices.swap(i, j);
+  }
+  indices.truncate(k);
+  indices
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use crate::models::PausableOperation;
+use cosmwasm_std::{StdError, Timestamp};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -38,4 +39,112 @@ pub enum ContractError {
 
   #[error("InvalidWeight")]
   InvalidWeight {},
+
+  #[error("NoOracle")]
+  NoOracle {},
+
+  #[error("NoMatchingOracleRule")]
+  NoMatchingOracleRule {},
+
+  #[error("NotScalar")]
+  NotScalar {},
+
+  #[error("ScalarValueRequired")]
+  ScalarValueRequired {},
+
+  #[error("InvalidAllocations")]
+  InvalidAllocations {},
+
+  #[error("InvalidRanking")]
+  InvalidRanking {},
+
+  #[error("NotRanked")]
+  NotRanked {},
+
+  #[error("RankingRequired")]
+  RankingRequired {},
+
+  #[error("NoPubkey")]
+  NoPubkey {},
+
+  #[error("InvalidSignature")]
+  InvalidSignature {},
+
+  #[error("StaleAttestation")]
+  StaleAttestation {},
+
+  #[error("DuplicateAttestation")]
+  DuplicateAttestation {},
+
+  #[error("NoProvider")]
+  NoProvider {},
+
+  #[error("InvalidPayload")]
+  InvalidPayload {},
+
+  #[error("NoJuryPool")]
+  NoJuryPool {},
+
+  #[error("PanelAlreadySelected")]
+  PanelAlreadySelected {},
+
+  #[error("InvalidBeacon")]
+  InvalidBeacon {},
+
+  #[error("ScriptHashMismatch")]
+  ScriptHashMismatch {},
+
+  #[error("InvalidLogs")]
+  InvalidLogs {},
+
+  #[error("LogsTooLarge")]
+  LogsTooLarge {},
+
+  #[error("TooManyHooks")]
+  TooManyHooks {},
+
+  #[error("HookAlreadyRegistered")]
+  HookAlreadyRegistered {},
+
+  #[error("HookNotRegistered")]
+  HookNotRegistered {},
+
+  #[error("TrialFrozen")]
+  TrialFrozen {},
+
+  #[error("NotDraft")]
+  NotDraft {},
+
+  #[error("NoPendingOwner")]
+  NoPendingOwner {},
+
+  #[error("OwnershipProposalExpired")]
+  OwnershipProposalExpired {},
+
+  #[error("Paused: {operation} is paused until {until}")]
+  Paused {
+    operation: PausableOperation,
+    until: Timestamp,
+  },
+
+  #[error("PauseLimitExceeded: {operation} can be paused for {seconds_left} more seconds")]
+  PauseLimitExceeded {
+    operation: PausableOperation,
+    seconds_left: u64,
+  },
+
+  #[error("NotPaused")]
+  NotPaused {},
+
+  #[error("CancelReasonRequired")]
+  CancelReasonRequired {},
+
+  #[error("CancelReasonTooLong: reasons are limited to {max_len} characters")]
+  CancelReasonTooLong { max_len: usize },
+
+  #[error("CosignersRequired: {cosigners} of {quorum} juror co-signers once deliberations begin")]
+  CosignersRequired { cosigners: u32, quorum: u32 },
+
+  #[error("HasCosigned")]
+  HasCosigned {},
 }
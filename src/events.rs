@@ -0,0 +1,286 @@
+//! Typed events emitted alongside each action and status transition, so that
+//! indexers can follow the trial without re-querying its state. Chains prefix
+//! each event's type with `wasm-`, e.g. `wasm-vote`. Amounts are given in the
+//! trial's token, addresses in bech32, and rulings and allocations as JSON.
+
+use crate::models::{Allocation, Cancellation, PausableOperation, Ruling, Status, Style};
+use cosmwasm_std::{to_vec, Addr, Decimal, Event, StdError, StdResult, Timestamp, Uint128};
+use cw_utils::Expiration;
+use serde::Serialize;
+
+/// `voter`, `choice`, `weight` and `payment`.
+pub const VOTE: &str = "vote";
+/// `juror`, `ruling`, `logs_hash` and `script_hash`.
+pub const DECIDE: &str = "decide";
+/// `claimant`, `type` (`refund` or `reward`) and `amount`.
+pub const CLAIM: &str = "claim";
+/// `canceled_by`, `reason`, `refundable` and `bond`.
+pub const CANCEL: &str = "cancel";
+/// `juror` and `cosigners`, the number of co-signers so far.
+pub const COSIGN_CANCEL: &str = "cosign_cancel";
+/// `from`, `to` and, once decided, `winner`.
+pub const TRANSITION: &str = "transition";
+/// `allocations`, `claimable` and `juror_fees`, plus `winner` unless invalid.
+pub const VERDICT: &str = "verdict";
+/// `juror` and `amount`, for each fee paid out to a juror.
+pub const JUROR_FEE: &str = "juror_fee";
+/// `oracle` and `value`.
+pub const RESOLVE: &str = "resolve";
+/// `timestamp` and `ruling`.
+pub const SIGNED_RESULT: &str = "signed_result";
+/// `round` and `panel`, a comma-separated list of juror addresses.
+pub const SELECT_PANEL: &str = "select_panel";
+/// `hook`.
+pub const ADD_HOOK: &str = "add_hook";
+/// `hook`.
+pub const REMOVE_HOOK: &str = "remove_hook";
+/// `hook` and `error`.
+pub const HOOK_FAILED: &str = "hook_failed";
+/// Whichever of `prompt`, `choice_texts` and `style` were edited, and the
+/// recomputed `config_hash` if the trial has been published.
+pub const UPDATE_TRIAL: &str = "update_trial";
+/// `field`, one of `choices`, `jury`, `cancel_policy`, `verdict` or `script`.
+pub const CONFIGURE: &str = "configure";
+/// `config_hash`.
+pub const PUBLISH: &str = "publish";
+/// `owner`, `new_owner` and `expiry`.
+pub const PROPOSE_OWNER: &str = "propose_owner";
+/// `owner` and `previous_owner`.
+pub const ACCEPT_OWNERSHIP: &str = "accept_ownership";
+/// `previous_owner`.
+pub const RENOUNCE_OWNERSHIP: &str = "renounce_ownership";
+/// `guardian`, unless removed.
+pub const SET_GUARDIAN: &str = "set_guardian";
+/// `operation` (`vote`, `claim` or `decide`), `until` and `paused_by`.
+pub const PAUSE: &str = "pause";
+/// `operation` and `unpaused_by`.
+pub const UNPAUSE: &str = "unpause";
+
+pub fn vote(
+  voter: &Addr,
+  choice: u32,
+  weight: u32,
+  payment: Uint128,
+) -> Event {
+  Event::new(VOTE)
+    .add_attribute("voter", voter)
+    .add_attribute("choice", choice.to_string())
+    .add_attribute("weight", weight.to_string())
+    .add_attribute("payment", payment)
+}
+
+pub fn decide(
+  juror: &Addr,
+  ruling: &Ruling,
+  logs_hash: &str,
+  script_hash: &str,
+) -> StdResult<Event> {
+  Ok(
+    Event::new(DECIDE)
+      .add_attribute("juror", juror)
+      .add_attribute("ruling", json(ruling)?)
+      .add_attribute("logs_hash", logs_hash)
+      .add_attribute("script_hash", script_hash),
+  )
+}
+
+pub fn claim(
+  claimant: &Addr,
+  refund: bool,
+  amount: Uint128,
+) -> Event {
+  Event::new(CLAIM)
+    .add_attribute("claimant", claimant)
+    .add_attribute("type", if refund { "refund" } else { "reward" })
+    .add_attribute("amount", amount)
+}
+
+pub fn cancel(cancellation: &Cancellation) -> Event {
+  Event::new(CANCEL)
+    .add_attribute("canceled_by", &cancellation.canceled_by)
+    .add_attribute("reason", &cancellation.reason)
+    .add_attribute("refundable", cancellation.refundable)
+    .add_attribute("bond", cancellation.bond)
+}
+
+pub fn cosign_cancel(
+  juror: &Addr,
+  cosigners: u32,
+) -> Event {
+  Event::new(COSIGN_CANCEL)
+    .add_attribute("juror", juror)
+    .add_attribute("cosigners", cosigners.to_string())
+}
+
+pub fn transition(
+  from: &Status,
+  to: &Status,
+  winner: Option<u32>,
+) -> Event {
+  let event = Event::new(TRANSITION)
+    .add_attribute("from", from.to_string())
+    .add_attribute("to", to.to_string());
+  match winner {
+    Some(winner) => event.add_attribute("winner", winner.to_string()),
+    None => event,
+  }
+}
+
+pub fn verdict(
+  winner: Option<u32>,
+  allocations: &[Allocation],
+  claimable: Uint128,
+  juror_fees: Uint128,
+) -> StdResult<Event> {
+  let event = Event::new(VERDICT)
+    .add_attribute("allocations", json(&allocations)?)
+    .add_attribute("claimable", claimable)
+    .add_attribute("juror_fees", juror_fees);
+  Ok(match winner {
+    Some(winner) => event.add_attribute("winner", winner.to_string()),
+    None => event,
+  })
+}
+
+pub fn juror_fee(
+  juror: &Addr,
+  amount: Uint128,
+) -> Event {
+  Event::new(JUROR_FEE)
+    .add_attribute("juror", juror)
+    .add_attribute("amount", amount)
+}
+
+pub fn resolve(
+  oracle: &Addr,
+  value: Decimal,
+) -> Event {
+  Event::new(RESOLVE)
+    .add_attribute("oracle", oracle)
+    .add_attribute("value", value.to_string())
+}
+
+pub fn signed_result(
+  timestamp: Timestamp,
+  ruling: &Ruling,
+) -> StdResult<Event> {
+  Ok(
+    Event::new(SIGNED_RESULT)
+      .add_attribute("timestamp", timestamp.to_string())
+      .add_attribute("ruling", json(ruling)?),
+  )
+}
+
+pub fn select_panel(
+  round: u64,
+  panel: &[String],
+) -> Event {
+  Event::new(SELECT_PANEL)
+    .add_attribute("round", round.to_string())
+    .add_attribute("panel", panel.join(","))
+}
+
+pub fn add_hook(hook: &Addr) -> Event {
+  Event::new(ADD_HOOK).add_attribute("hook", hook)
+}
+
+pub fn remove_hook(hook: &Addr) -> Event {
+  Event::new(REMOVE_HOOK).add_attribute("hook", hook)
+}
+
+pub fn hook_failed(
+  hook: &str,
+  error: &str,
+) -> Event {
+  Event::new(HOOK_FAILED)
+    .add_attribute("hook", hook)
+    .add_attribute("error", error)
+}
+
+pub fn update_trial(
+  prompt: Option<&str>,
+  choice_texts: Option<&[String]>,
+  style: Option<&Style>,
+  config_hash: Option<&str>,
+) -> StdResult<Event> {
+  let mut event = Event::new(UPDATE_TRIAL);
+  if let Some(prompt) = prompt {
+    event = event.add_attribute("prompt", prompt);
+  }
+  if let Some(choice_texts) = choice_texts {
+    event = event.add_attribute("choice_texts", json(choice_texts)?);
+  }
+  if let Some(style) = style {
+    event = event.add_attribute("style", json(style)?);
+  }
+  if let Some(config_hash) = config_hash {
+    event = event.add_attribute("config_hash", config_hash);
+  }
+  Ok(event)
+}
+
+pub fn configure(field: &str) -> Event {
+  Event::new(CONFIGURE).add_attribute("field", field)
+}
+
+pub fn publish(config_hash: &str) -> Event {
+  Event::new(PUBLISH).add_attribute("config_hash", config_hash)
+}
+
+pub fn propose_owner(
+  owner: &Addr,
+  new_owner: &Addr,
+  expiry: &Expiration,
+) -> Event {
+  Event::new(PROPOSE_OWNER)
+    .add_attribute("owner", owner)
+    .add_attribute("new_owner", new_owner)
+    .add_attribute("expiry", expiry.to_string())
+}
+
+pub fn accept_ownership(
+  previous_owner: Option<&Addr>,
+  owner: &Addr,
+) -> Event {
+  let event = Event::new(ACCEPT_OWNERSHIP).add_attribute("owner", owner);
+  match previous_owner {
+    Some(previous_owner) => event.add_attribute("previous_owner", previous_owner),
+    None => event,
+  }
+}
+
+pub fn renounce_ownership(previous_owner: &Addr) -> Event {
+  Event::new(RENOUNCE_OWNERSHIP).add_attribute("previous_owner", previous_owner)
+}
+
+pub fn set_guardian(guardian: Option<&Addr>) -> Event {
+  let event = Event::new(SET_GUARDIAN);
+  match guardian {
+    Some(guardian) => event.add_attribute("guardian", guardian),
+    None => event,
+  }
+}
+
+pub fn pause(
+  operation: PausableOperation,
+  until: Timestamp,
+  paused_by: &Addr,
+) -> Event {
+  Event::new(PAUSE)
+    .add_attribute("operation", operation.to_string())
+    .add_attribute("until", until.to_string())
+    .add_attribute("paused_by", paused_by)
+}
+
+pub fn unpause(
+  operation: PausableOperation,
+  unpaused_by: &Addr,
+) -> Event {
+  Event::new(UNPAUSE)
+    .add_attribute("operation", operation.to_string())
+    .add_attribute("unpaused_by", unpaused_by)
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> StdResult<String> {
+  String::from_utf8(to_vec(value)?).map_err(|err| StdError::generic_err(err.to_string()))
+}
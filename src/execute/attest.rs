@@ -0,0 +1,80 @@
+use crate::{
+  error::ContractError,
+  execute::decide::{deliberate, validate_ruling, Decision},
+  models::Attestation,
+  state::{JURORS, TRIAL},
+  util::attestation_digest,
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// A relayer submits a batch of rulings signed by jurors, sparing each juror
+/// from sending their own `Decide` tx. Every signature is checked against the
+/// public key registered for the juror, after which the batch is deliberated
+/// just as if each juror had decided for themselves. An attestation can't
+/// replace a ruling the juror has since changed, so it can't be replayed, and a
+/// batch can attest to each juror's ruling only once.
+pub fn submit_attestations(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  attestations: Vec<Attestation>,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+
+  if attestations.is_empty() {
+    return Err(ContractError::ValidationError {});
+  }
+
+  let n_attestations = attestations.len();
+  let mut decisions: Vec<Decision> = Vec::with_capacity(n_attestations);
+  for attestation in attestations {
+    // the checks below are made against the jury as it was before the batch,
+    // so a second attestation for the same juror could override the first
+    if decisions
+      .iter()
+      .any(|decision| decision.juror == attestation.juror)
+    {
+      return Err(ContractError::DuplicateAttestation {});
+    }
+    let juror = JURORS
+      .may_load(deps.storage, attestation.juror.clone())?
+      .ok_or(ContractError::NotAuthorized {})?;
+    let pubkey = juror.pubkey.as_ref().ok_or(ContractError::NoPubkey {})?;
+
+    // the signature covers the ruling as submitted by the juror, before it's
+    // put into canonical form
+    let digest = attestation_digest(
+      &env.contract.address,
+      &attestation.ruling,
+      &attestation.logs.hash,
+      &attestation.script_hash,
+    )?;
+    let is_valid = deps
+      .api
+      .secp256k1_verify(&digest, &attestation.signature, pubkey)
+      .map_err(|_| ContractError::InvalidSignature {})?;
+    if !is_valid {
+      return Err(ContractError::InvalidSignature {});
+    }
+
+    // a juror who has already decided can only be attested to the same
+    // ruling and logs, so that an old attestation can't undo a newer decision
+    let ruling = validate_ruling(&trial, attestation.ruling)?;
+    if juror.has_decided()
+      && (juror.ruling.as_ref() != Some(&ruling)
+        || juror.logs.as_ref().map(|logs| &logs.hash) != Some(&attestation.logs.hash))
+    {
+      return Err(ContractError::StaleAttestation {});
+    }
+
+    decisions.push(Decision {
+      juror: attestation.juror,
+      ruling,
+      logs: attestation.logs,
+      script_hash: attestation.script_hash,
+    });
+  }
+
+  let resp = deliberate(deps, env, &info.sender, trial, decisions)?;
+  Ok(resp.add_attributes(vec![attr("attestations", n_attestations.to_string())]))
+}
@@ -1,26 +1,173 @@
 use crate::{
   error::ContractError,
-  models::Status,
-  state::{CANCEL_REASON, TRIAL},
+  events,
+  models::{Cancellation, HistoryAction, Status, Token},
+  state::{
+    append_history, transition, CANCELLATION, CANCEL_COSIGNERS, JURORS, MAX_CANCEL_REASON_LEN,
+    STATS, TRIAL,
+  },
+  util::{build_cw20_transfer_msg, merge_response, validate_cw20_funds, validate_native_funds},
 };
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128};
 
+/// The owner cancels the trial, giving a reason of up to
+/// `MAX_CANCEL_REASON_LEN` characters. Once deliberations have begun, a quorum
+/// of jurors must first co-sign the cancellation, unless deliberations have
+/// expired. If the trial has been voted on,
+/// the owner must also post the bond required by the trial's cancel policy,
+/// which is paid out to voters along with their refunds.
 pub fn cancel(
   deps: DepsMut,
-  _env: Env,
+  env: Env,
   info: MessageInfo,
-  reason: &String,
+  reason: &str,
 ) -> Result<Response, ContractError> {
   let mut trial = TRIAL.load(deps.storage)?;
 
   // only the trial's owner can cancel;
   // only games that are active or deciding can be canceled
-  if info.sender == trial.owner && trial.can_be_canceled() {
-    trial.status = Status::Dismissed;
-    TRIAL.save(deps.storage, &trial)?;
-    CANCEL_REASON.save(deps.storage, reason)?;
-    Ok(Response::new().add_attributes(vec![attr("action", "cancel")]))
+  if !(trial.is_owner(&info.sender) && trial.can_be_canceled()) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if reason.trim().is_empty() {
+    return Err(ContractError::CancelReasonRequired {});
+  }
+  if reason.chars().count() > MAX_CANCEL_REASON_LEN {
+    return Err(ContractError::CancelReasonTooLong {
+      max_len: MAX_CANCEL_REASON_LEN,
+    });
+  }
+
+  // once deliberations begin, the owner can't cancel on their own, unless the
+  // jury has failed to decide in time. a trial without jurors can't be
+  // canceled at all until then, but hangs once deliberations expire.
+  let cosigners = CANCEL_COSIGNERS
+    .keys(deps.storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<Addr>>>()?;
+  let n_jurors = JURORS
+    .keys(deps.storage, None, None, Order::Ascending)
+    .count() as u32;
+  if trial.has_deliberations_begun(env.block.time) && !trial.is_deliberation_expired(env.block.time)
+  {
+    let quorum = trial.cancel_policy.quorum(n_jurors);
+    if (cosigners.len() as u32) < quorum {
+      return Err(ContractError::CosignersRequired {
+        cosigners: cosigners.len() as u32,
+        quorum,
+      });
+    }
+  }
+
+  // the bond is only owed if there are voters to pay it out to
+  let bond = if trial.weight > 0 {
+    trial.cancel_policy.bond
   } else {
-    Err(ContractError::NotAuthorized {})
+    Uint128::zero()
+  };
+  let mut bond_response = Response::new();
+  if !bond.is_zero() {
+    match trial.token.clone() {
+      Token::Native { denom } => validate_native_funds(&info.funds, bond, &denom)?,
+      Token::Cw20 {
+        address: cw20_token_address,
+      } => {
+        validate_cw20_funds(&deps, &info.sender, bond, &cw20_token_address)?;
+        bond_response = bond_response.add_submessage(build_cw20_transfer_msg(
+          &info.sender,
+          &env.contract.address,
+          &cw20_token_address,
+          bond,
+        )?);
+      },
+    }
+  }
+
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Cancel {
+      reason: reason.to_owned(),
+    },
+  )?;
+  let response = transition(
+    deps.storage,
+    &env,
+    &info.sender,
+    &mut trial,
+    Status::Dismissed,
+  )?;
+  TRIAL.save(deps.storage, &trial)?;
+
+  // record who canceled, when and why, along with the total amount that
+  // voters are now entitled to reclaim.
+  let cancellation = Cancellation {
+    canceled_by: info.sender.clone(),
+    canceled_at: env.block.time,
+    height: env.block.height,
+    reason: reason.to_owned(),
+    refundable: trial.pot() + bond,
+    bond,
+    cosigners,
+  };
+  CANCELLATION.save(deps.storage, &cancellation)?;
+
+  // every voter is now owed a complete refund
+  STATS.update(deps.storage, |mut stats| -> Result<_, ContractError> {
+    stats.liabilities = cancellation.refundable;
+    stats.claimants_remaining = stats.voter_count;
+    Ok(stats)
+  })?;
+
+  Ok(
+    merge_response(response, bond_response)
+      .add_event(events::cancel(&cancellation))
+      .add_attributes(vec![
+        attr("action", "cancel"),
+        attr("canceled_by", cancellation.canceled_by.to_string()),
+        attr("canceled_at", cancellation.canceled_at.to_string()),
+        attr("reason", cancellation.reason),
+        attr("refundable", cancellation.refundable.to_string()),
+      ]),
+  )
+}
+
+/// A juror co-signs the owner's cancellation of the trial, which a quorum of
+/// jurors must do before the owner can cancel once deliberations have begun.
+pub fn cosign_cancel(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  if !(trial.can_be_canceled() && trial.has_deliberations_begun(env.block.time)) {
+    return Err(ContractError::NotDeciding {});
+  }
+  // the juror must be a registered member of the jury
+  if !JURORS.has(deps.storage, info.sender.clone()) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if CANCEL_COSIGNERS.has(deps.storage, info.sender.clone()) {
+    return Err(ContractError::HasCosigned {});
   }
+
+  CANCEL_COSIGNERS.save(deps.storage, info.sender.clone(), &true)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::CosignCancel {},
+  )?;
+  let cosigners = CANCEL_COSIGNERS
+    .keys(deps.storage, None, None, Order::Ascending)
+    .count() as u32;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "cosign_cancel"),
+        attr("cosigners", cosigners.to_string()),
+      ])
+      .add_event(events::cosign_cancel(&info.sender, cosigners)),
+  )
 }
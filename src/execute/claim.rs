@@ -1,15 +1,23 @@
 use crate::{
   error::ContractError,
-  models::Token,
-  state::{HAS_CLAIMED, TRIAL, VOTERS_TOTAL_CLAIM_AMOUNT, VOTES},
-  util::{build_cw20_transfer_msg, build_native_send_msg},
+  events,
+  execute::decide::hang,
+  models::{HistoryAction, PausableOperation, Status, Token},
+  state::{
+    append_history, ensure_not_paused, transition, ALLOCATIONS, CANCELLATION, HAS_CLAIMED, STATS,
+    TRIAL, VOTERS_TOTAL_CLAIM_AMOUNT, VOTER_WEIGHTS, VOTES,
+  },
+  util::{build_cw20_transfer_msg, build_native_send_msg, merge_response},
 };
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, Storage, Uint128};
 
-/// A wallet can claim under 2 conditions: (1) the game was canceled or hung, or
-/// (2) the winning choice has been decided. If the game was canceled, then the
-/// wallet can reclaim their funds. However, if a wallet won, then the size of
-/// the prize is proportional to the weight of their vote. For example, if they
+/// A wallet can claim under 2 conditions: (1) the game was canceled, hung or
+/// ruled invalid, or (2) the winning choice has been decided. If the game was
+/// canceled, or if deliberations expired without a verdict, then the wallet
+/// can reclaim their funds, along with their share of any bond posted by the
+/// owner. However, if a wallet won, then the size of
+/// the prize is proportional to the weight of their vote, within the share of
+/// the pot allocated to each winning choice they voted for. For example, if they
 /// voted with a weight of 5, then their portion of the winnings is 5x the
 /// portion allocated to someone whose vote carried a weight of 1. The trial
 /// must be in the "decided" state to execute this method, and only winning
@@ -19,25 +27,63 @@ pub fn claim(
   env: Env,
   info: MessageInfo,
 ) -> Result<Response, ContractError> {
+  ensure_not_paused(deps.storage, &env, PausableOperation::Claim)?;
   let voter_addr = &info.sender;
-  let trial = TRIAL.load(deps.storage)?;
+  let mut trial = TRIAL.load(deps.storage)?;
 
-  // if the game was canceled or hung, then send the voter a complete refund.
-  if trial.has_been_canceled() || trial.has_hung_jury() {
-    let mut voter_weight = 0u32;
-    for choice_index in 0..trial.choices.len() {
-      if let Some(vote) = VOTES.may_load(deps.storage, (choice_index as u32, voter_addr.clone()))? {
-        voter_weight += vote.weight;
-      }
+  // if the deliberation period ended without a verdict, e.g. because the jury
+  // went silent or the oracle's value matched no rule, then the jury is hung.
+  let mut response = Response::new();
+  if trial.can_be_canceled() && trial.is_deliberation_expired(env.block.time) {
+    if trial.is_active() {
+      response = transition(
+        deps.storage,
+        &env,
+        voter_addr,
+        &mut trial,
+        Status::Deliberating,
+      )?;
     }
+    response = merge_response(response, hang(deps.storage, &env, voter_addr, &mut trial)?);
+    TRIAL.save(deps.storage, &trial)?;
+  }
+
+  // if the game was canceled or hung, then send the voter a complete refund. if
+  // the jury ruled the trial invalid, then refund the voter's portion of the
+  // pot, net of the jury's reduced fees.
+  if trial.has_been_canceled() || trial.has_hung_jury() || trial.was_ruled_invalid() {
+    let voter_weight = VOTER_WEIGHTS
+      .may_load(deps.storage, voter_addr.clone())?
+      .unwrap_or(0);
     if voter_weight > 0 {
+      // abort if the voter has already claimed their refund
+      if HAS_CLAIMED
+        .may_load(deps.storage, voter_addr.clone())?
+        .unwrap_or(false)
+      {
+        return Err(ContractError::HasClaimed {});
+      }
+      HAS_CLAIMED.save(deps.storage, voter_addr.clone(), &true)?;
       // build a response with the necessary transfer msg/submsg for the refund
-      let claim_amount = Uint128::from(voter_weight) * trial.price;
-      let response = Response::new().add_attributes(vec![
-        attr("action", "claim"),
-        attr("type", "refund"),
-        attr("amount", claim_amount.to_string()),
-      ]);
+      let claim_amount = if trial.was_ruled_invalid() {
+        VOTERS_TOTAL_CLAIM_AMOUNT
+          .load(deps.storage)?
+          .multiply_ratio(voter_weight, trial.weight)
+      } else if trial.has_been_canceled() {
+        // along with their share of the bond posted by the owner
+        let bond = CANCELLATION.load(deps.storage)?.bond;
+        Uint128::from(voter_weight) * trial.price + bond.multiply_ratio(voter_weight, trial.weight)
+      } else {
+        Uint128::from(voter_weight) * trial.price
+      };
+      record_claim(deps.storage, &env, voter_addr, claim_amount, 1, true)?;
+      let response = response
+        .add_attributes(vec![
+          attr("action", "claim"),
+          attr("type", "refund"),
+          attr("amount", claim_amount.to_string()),
+        ])
+        .add_event(events::claim(voter_addr, true, claim_amount));
       Ok(match trial.token.clone() {
         Token::Native { denom } => {
           response.add_message(build_native_send_msg(&info.sender, &denom, claim_amount)?)
@@ -57,60 +103,103 @@ pub fn claim(
     }
   }
   // otherwise, ssume the tx sender is trying to claim their prize
-  else if let Some(choice_index) = trial.winner {
+  else if trial.winner.is_some() {
     if !trial.has_verdict() {
       // even if the winner choice index is set, still check the trial's status
       // and abort if it isn't "decided"
       return Err(ContractError::NotDecided {});
     }
-    // get metadata for the choice that won...
-    if let Some(choice) = trial.choices.get(choice_index as usize) {
+    // abort if the voter has already claimed
+    if HAS_CLAIMED
+      .may_load(deps.storage, voter_addr.clone())?
+      .unwrap_or(false)
+    {
+      return Err(ContractError::HasClaimed {});
+    }
+    // sum the claimant's portion of each winning choice's share of the pot
+    let voters_total_amount = VOTERS_TOTAL_CLAIM_AMOUNT.load(deps.storage)?;
+    let mut claim_amount = Uint128::zero();
+    let mut n_positions = 0u32;
+    for allocation in ALLOCATIONS.load(deps.storage)?.iter() {
+      // get metadata for the choice that won...
+      let choice = trial
+        .choices
+        .get(allocation.choice as usize)
+        // shouldn't be possible. this means that somehow an invalid choice
+        // index was allocated a share of the pot when rendering the verdict.
+        .ok_or(ContractError::InvalidChoice {})?;
       // get the claimant's voting record...
-      if let Some(vote) = VOTES.may_load(deps.storage, (choice_index, voter_addr.clone()))? {
-        // abort if the voter has already claimed
-        if HAS_CLAIMED
-          .may_load(deps.storage, voter_addr.clone())?
-          .unwrap_or(false)
-        {
-          Err(ContractError::HasClaimed {})
-        } else {
-          // persist the fact that the wallet has now claimed
-          HAS_CLAIMED.save(deps.storage, voter_addr.clone(), &true)?;
-          // build a response with the necessary transfer msg/submsg
-          let voters_total_amount = VOTERS_TOTAL_CLAIM_AMOUNT.load(deps.storage)?;
-          let claim_amount = voters_total_amount * Uint128::from(vote.weight / choice.weight);
-
-          let response = Response::new().add_attributes(vec![
-            attr("action", "claim"),
-            attr("type", "reward"),
-            attr("amount", claim_amount.to_string()),
-          ]);
-
-          Ok(match trial.token.clone() {
-            Token::Native { denom } => {
-              response.add_message(build_native_send_msg(&info.sender, &denom, claim_amount)?)
-            },
-            Token::Cw20 {
-              address: cw20_token_address,
-            } => response.add_submessage(build_cw20_transfer_msg(
-              &env.contract.address,
-              &info.sender,
-              &cw20_token_address,
-              claim_amount,
-            )?),
-          })
-        }
-      } else {
-        // the sender didn't vote for the winner
-        Err(ContractError::NotAuthorized {})
+      if let Some(vote) = VOTES.may_load(deps.storage, (allocation.choice, voter_addr.clone()))? {
+        let choice_amount = voters_total_amount * allocation.share;
+        claim_amount += choice_amount.multiply_ratio(vote.weight, choice.weight);
+        n_positions += 1;
       }
-    } else {
-      // shouldn't be possible. this means that somehow an invalid choice index
-      // was saved as the winner in the `decide` function.
-      Err(ContractError::InvalidChoice {})
     }
+    if n_positions == 0 {
+      // the sender didn't vote for a winner
+      return Err(ContractError::NotAuthorized {});
+    }
+    // persist the fact that the wallet has now claimed
+    HAS_CLAIMED.save(deps.storage, voter_addr.clone(), &true)?;
+    record_claim(
+      deps.storage,
+      &env,
+      voter_addr,
+      claim_amount,
+      n_positions,
+      false,
+    )?;
+
+    // build a response with the necessary transfer msg/submsg
+    let response = Response::new()
+      .add_attributes(vec![
+        attr("action", "claim"),
+        attr("type", "reward"),
+        attr("amount", claim_amount.to_string()),
+      ])
+      .add_event(events::claim(voter_addr, false, claim_amount));
+
+    Ok(match trial.token.clone() {
+      Token::Native { denom } => {
+        response.add_message(build_native_send_msg(&info.sender, &denom, claim_amount)?)
+      },
+      Token::Cw20 {
+        address: cw20_token_address,
+      } => response.add_submessage(build_cw20_transfer_msg(
+        &env.contract.address,
+        &info.sender,
+        &cw20_token_address,
+        claim_amount,
+      )?),
+    })
   } else {
     // the jury is still out, so nothing can be claimed.
     Err(ContractError::NotDecided {})
   }
 }
+
+/// Move a claimed amount from outstanding liabilities to the claimed total and
+/// record the claim in the audit log. Claimants are counted by position, so a
+/// reward covering several winning choices settles several claimants.
+fn record_claim(
+  storage: &mut dyn Storage,
+  env: &Env,
+  claimant: &Addr,
+  amount: Uint128,
+  n_claimants: u32,
+  refund: bool,
+) -> Result<(), ContractError> {
+  STATS.update(storage, |mut stats| -> Result<_, ContractError> {
+    stats.total_claimed += amount;
+    stats.liabilities = stats.liabilities.saturating_sub(amount);
+    stats.claimants_remaining = stats.claimants_remaining.saturating_sub(n_claimants);
+    Ok(stats)
+  })?;
+  append_history(
+    storage,
+    env,
+    claimant,
+    HistoryAction::Claim { amount, refund },
+  )?;
+  Ok(())
+}
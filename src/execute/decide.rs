@@ -1,10 +1,26 @@
 use crate::{
   error::ContractError,
-  models::{Juror, Status, Token},
-  state::{JURORS, TRIAL, VOTERS_TOTAL_CLAIM_AMOUNT},
-  util::{build_cw20_transfer_msg, build_native_send_msg},
+  events,
+  execute::verdict::{render_invalid_verdict, render_verdict, ruling_allocations},
+  models::{Allocation, HistoryAction, Juror, Logs, PausableOperation, Ruling, Status, Trial},
+  state::{
+    append_history, ensure_not_paused, is_sha256_hex, transition, DEFAULT_MAX_INLINE_LOGS, JURORS,
+    JUROR_LOGS, STATS, TRIAL,
+  },
+  util::{hash_logs, merge_response},
 };
-use cosmwasm_std::{attr, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response, SubMsg, Uint128};
+use cosmwasm_std::{
+  attr, Addr, Decimal, DepsMut, Env, Event, MessageInfo, Order, Response, Storage,
+};
+
+/// A juror's ruling along with the logs of the script run that produced it,
+/// and the hash of the script, as echoed by the juror.
+pub struct Decision {
+  pub juror: Addr,
+  pub ruling: Ruling,
+  pub logs: Logs,
+  pub script_hash: String,
+}
 
 /// A jury uploads the result of running the decision script. Once all jurors
 /// have invoked this message and agree on the outcome (i.e. all "choice" values
@@ -15,108 +31,364 @@ pub fn decide(
   env: Env,
   info: MessageInfo,
   choice_index: usize,
-  script_logs: &String,
+  logs: Logs,
+  script_hash: &str,
 ) -> Result<Response, ContractError> {
-  if let Some(mut juror) = JURORS.may_load(deps.storage, info.sender.clone())? {
-    let mut trial = TRIAL.load(deps.storage)?;
+  decide_ruling(
+    deps,
+    env,
+    info,
+    Ruling::Choice(choice_index as u32),
+    logs,
+    script_hash,
+  )
+}
 
-    // abort if we're not in the deciding state
-    if !trial.is_in_deliberations() {
-      return Err(ContractError::NotDeciding {});
-    }
-    // abort if the jury is casting a vote for an invalid choice
-    if choice_index > trial.choices.len() - 1 {
-      return Err(ContractError::InvalidChoice {});
-    }
-    // abort if the deliberation period has ended
-    let end_time = trial
-      .verdict
-      .start
-      .plus_seconds(60u64 * (trial.verdict.minutes as u64));
-    if env.block.time > end_time {
-      return Err(ContractError::DeliberationsExpired {});
-    }
+/// In a scalar trial, a jury uploads the numeric result of running the decision
+/// script, which maps to a choice by the trial's scalar ranges. All jurors must
+/// agree on the exact value, since it determines how the pot is split when
+/// payouts are linear.
+pub fn decide_value(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  value: Decimal,
+  logs: Logs,
+  script_hash: &str,
+) -> Result<Response, ContractError> {
+  decide_ruling(deps, env, info, Ruling::Value(value), logs, script_hash)
+}
 
-    // persist juror's choice and script execution logs
-    juror.choice = Some(choice_index as u32);
-    juror.logs = Some(script_logs.clone());
-
-    JURORS.save(deps.storage, info.sender.clone(), &juror)?;
-
-    // determine if the jury is now hung. The jury is "hung" when the current
-    // juror's "choice" does not agree with all extant jury choices.
-    let mut can_be_decided = true;
-    let mut jurors: Vec<Juror> = vec![];
-    for juror_result in JURORS.range(deps.storage, None, None, Order::Ascending) {
-      if let Some((_juror_addr, juror)) = juror_result.ok() {
-        if let Some(existing_choice_index) = juror.choice {
-          if existing_choice_index != choice_index as u32 {
-            trial.status = Status::HungJury;
-            can_be_decided = false;
-            break;
-          } else {
-            jurors.push(juror);
-          }
-        } else {
-          // the game can't be decided unless 100% of jurors are present,
-          // and this juror hasn't made their choice yet
-          can_be_decided = false;
-          break;
-        }
+/// A jury names several winning choices, each with a share of the pot, e.g. a
+/// 50/50 split in case of a tie. Shares must be positive and sum to 1, and each
+/// choice may only be named once. All jurors must agree on the same split.
+pub fn decide_split(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  allocations: Vec<Allocation>,
+  logs: Logs,
+  script_hash: &str,
+) -> Result<Response, ContractError> {
+  decide_ruling(
+    deps,
+    env,
+    info,
+    Ruling::Split(allocations),
+    logs,
+    script_hash,
+  )
+}
+
+/// In a ranked trial, a jury uploads the choices that placed, from first to
+/// last, one for each place in the trial's payout table. All jurors must agree
+/// on the same ranking.
+pub fn decide_ranking(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  ranking: Vec<u32>,
+  logs: Logs,
+  script_hash: &str,
+) -> Result<Response, ContractError> {
+  decide_ruling(deps, env, info, Ruling::Ranking(ranking), logs, script_hash)
+}
+
+/// A jury rules that the trial's prompt can't be answered, e.g. because the
+/// event was canceled or the question turned out to be ambiguous. If all jurors
+/// agree, voters are refunded, and jurors receive a reduced fee.
+pub fn decide_invalid(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  logs: Logs,
+  script_hash: &str,
+) -> Result<Response, ContractError> {
+  decide_ruling(deps, env, info, Ruling::Invalid, logs, script_hash)
+}
+
+/// Validate and record the tx sender's own ruling.
+fn decide_ruling(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  ruling: Ruling,
+  logs: Logs,
+  script_hash: &str,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  let ruling = validate_ruling(&trial, ruling)?;
+  deliberate(
+    deps,
+    env,
+    &info.sender,
+    trial,
+    vec![Decision {
+      juror: info.sender.clone(),
+      ruling,
+      logs,
+      script_hash: script_hash.to_owned(),
+    }],
+  )
+}
+
+/// Check that a ruling is of the kind expected by the trial and refers only to
+/// existing choices. Returns the ruling in canonical form, so that jurors who
+/// agree on it can be compared for equality.
+pub fn validate_ruling(
+  trial: &Trial,
+  ruling: Ruling,
+) -> Result<Ruling, ContractError> {
+  let is_scalar = trial.verdict.scalar.is_some();
+  let is_ranked = trial.verdict.payout_table.is_some();
+  match ruling {
+    // scalar trials are decided by value and ranked trials by ranking the
+    // choices, rather than by choice
+    Ruling::Choice(_) | Ruling::Split(_) if is_scalar => Err(ContractError::ScalarValueRequired {}),
+    Ruling::Choice(_) | Ruling::Split(_) if is_ranked => Err(ContractError::RankingRequired {}),
+    Ruling::Choice(choice_index) => {
+      // abort if the jury is casting a vote for an invalid choice
+      if choice_index as usize >= trial.choices.len() {
+        return Err(ContractError::InvalidChoice {});
       }
-    }
-    let mut cw20_jury_transfer_submsgs: Vec<SubMsg> = Vec::with_capacity(jurors.len());
-    let mut native_jury_send_msgs: Vec<CosmosMsg> = Vec::with_capacity(jurors.len());
-    // if not hung, transition the state to Decided because all jurors are in
-    // agreement winning choice.
-    if can_be_decided {
-      trial.status = Status::HasVerdict;
-      trial.winner = Some(choice_index as u32);
-
-      let total = trial.price * Uint128::from(trial.weight);
-      let mut jurors_total_claim_amount = Uint128::zero();
-
-      // build transfer msgs for auto-sending rewards to jury members
-      if total > Uint128::zero() {
-        match trial.token.clone() {
-          Token::Cw20 { address } => {
-            for juror in jurors.iter() {
-              let amount = Uint128::from(juror.pct) * total / Uint128::from(100u128);
-              jurors_total_claim_amount += amount;
-              cw20_jury_transfer_submsgs.push(build_cw20_transfer_msg(
-                &env.contract.address,
-                &juror.address,
-                &address,
-                amount,
-              )?)
-            }
-          },
-          Token::Native { denom } => {
-            for juror in jurors.iter() {
-              let amount = Uint128::from(juror.pct) * total / Uint128::from(100u128);
-              jurors_total_claim_amount += amount;
-              native_jury_send_msgs.push(build_native_send_msg(&juror.address, &denom, amount)?);
-            }
-          },
+      Ok(Ruling::Choice(choice_index))
+    },
+    Ruling::Value(_) if !is_scalar => Err(ContractError::NotScalar {}),
+    Ruling::Split(mut allocations) => {
+      // sort by choice so that jurors naming the same split in a different
+      // order are still in agreement
+      allocations.sort_by_key(|allocation| allocation.choice);
+      validate_allocations(trial, &allocations)?;
+      Ok(Ruling::Split(allocations))
+    },
+    Ruling::Ranking(ranking) => {
+      let payout_table = trial
+        .verdict
+        .payout_table
+        .as_ref()
+        .ok_or(ContractError::NotRanked {})?;
+      if ranking.len() != payout_table.len() {
+        return Err(ContractError::InvalidRanking {});
+      }
+      for (i, choice_index) in ranking.iter().enumerate() {
+        if *choice_index as usize >= trial.choices.len() {
+          return Err(ContractError::InvalidChoice {});
+        }
+        if ranking[..i].contains(choice_index) {
+          return Err(ContractError::InvalidRanking {});
         }
-        // save the remainder of the rewards eligible for claims by winning voters
-        VOTERS_TOTAL_CLAIM_AMOUNT.save(deps.storage, &(total - jurors_total_claim_amount))?;
       }
+      Ok(Ruling::Ranking(ranking))
+    },
+    Ruling::Value(_) | Ruling::Invalid => Ok(ruling),
+  }
+}
+
+/// Record one or more jurors' decisions and, if the jury is now hung or
+/// unanimous, move the trial into its final state. Decisions must already be
+/// validated. Aborts while decisions are paused.
+pub fn deliberate(
+  deps: DepsMut,
+  env: Env,
+  actor: &Addr,
+  mut trial: Trial,
+  decisions: Vec<Decision>,
+) -> Result<Response, ContractError> {
+  ensure_not_paused(deps.storage, &env, PausableOperation::Decide)?;
+  let mut response = Response::new().add_attributes(vec![attr("action", "decide")]);
+
+  // deliberations begin once voting has closed
+  if trial.is_active() && trial.is_voting_closed(env.block.time) {
+    response = merge_response(
+      response,
+      transition(deps.storage, &env, actor, &mut trial, Status::Deliberating)?,
+    );
+  }
+  // abort if we're not in the deciding state
+  if !trial.is_in_deliberations() {
+    return Err(ContractError::NotDeciding {});
+  }
+  // abort if the deliberation period has ended
+  if trial.is_deliberation_expired(env.block.time) {
+    return Err(ContractError::DeliberationsExpired {});
+  }
+
+  for decision in decisions {
+    response = response.add_event(record_decision(deps.storage, &env, &trial, decision)?);
+  }
+
+  // determine if the jury is now hung. The jury is "hung" when any juror's
+  // decision does not agree with the others.
+  let mut can_be_decided = true;
+  let mut is_hung = false;
+  let mut jurors: Vec<Juror> = vec![];
+  for juror_result in JURORS.range(deps.storage, None, None, Order::Ascending) {
+    let (_juror_addr, juror) = juror_result?;
+    if juror.has_decided() {
+      if jurors
+        .first()
+        .map_or(false, |first| !first.agrees_with(&juror))
+      {
+        is_hung = true;
+        can_be_decided = false;
+        break;
+      }
+      jurors.push(juror);
+    } else {
+      // the game can't be decided unless 100% of jurors are present
+      can_be_decided = false;
     }
-    // persist all accumulated updates to Trial and return response with
-    // msgs for performing transfers to jury members
-    TRIAL.save(deps.storage, &trial)?;
+  }
+  if is_hung {
+    response = merge_response(response, hang(deps.storage, &env, actor, &mut trial)?);
+  }
+  // if not hung, transition the state to Decided because all jurors are in
+  // agreement winning choice, or to Invalid if they agree there is none.
+  if can_be_decided {
+    let ruling = jurors
+      .first()
+      .and_then(|juror| juror.ruling.clone())
+      .ok_or(ContractError::NotDeciding {})?;
+    let allocations = ruling_allocations(&trial, ruling)?;
+    response = merge_response(
+      response,
+      if let Some(allocations) = allocations {
+        render_verdict(deps.storage, &env, actor, &mut trial, allocations, &jurors)?
+      } else {
+        render_invalid_verdict(deps.storage, &env, actor, &mut trial, &jurors)?
+      },
+    );
+  }
+  // persist all accumulated updates to Trial and return response with msgs
+  // for notifying hooks and performing transfers to jury members
+  TRIAL.save(deps.storage, &trial)?;
 
-    let mut response = Response::new().add_attributes(vec![attr("action", "decide")]);
+  Ok(response)
+}
 
-    if cw20_jury_transfer_submsgs.len() > 0 {
-      response = response.add_submessages(cw20_jury_transfer_submsgs)
-    } else if native_jury_send_msgs.len() > 0 {
-      response = response.add_messages(native_jury_send_msgs);
+/// Move the trial into the `HungJury` state, in which every voter is owed a
+/// complete refund. The caller must save the trial.
+pub fn hang(
+  storage: &mut dyn Storage,
+  env: &Env,
+  actor: &Addr,
+  trial: &mut Trial,
+) -> Result<Response, ContractError> {
+  let response = transition(storage, env, actor, trial, Status::HungJury)?;
+  STATS.update(storage, |mut stats| -> Result<_, ContractError> {
+    stats.liabilities = stats.total_escrowed;
+    stats.claimants_remaining = stats.voter_count;
+    Ok(stats)
+  })?;
+  Ok(response)
+}
+
+/// Persist a juror's ruling and script execution logs, along with the choice
+/// it maps to, if any. Returns the decision's event.
+fn record_decision(
+  storage: &mut dyn Storage,
+  env: &Env,
+  trial: &Trial,
+  decision: Decision,
+) -> Result<Event, ContractError> {
+  // the juror must be a registered member of the jury
+  let mut juror = JURORS
+    .may_load(storage, decision.juror.clone())?
+    .ok_or(ContractError::NotAuthorized {})?;
+  // and must have run the trial's script
+  if decision.script_hash != trial.verdict.script.hash {
+    return Err(ContractError::ScriptHashMismatch {});
+  }
+  validate_logs(trial, &decision.logs)?;
+
+  juror.choice = match (&decision.ruling, &trial.verdict.scalar) {
+    (Ruling::Choice(choice_index), _) => Some(*choice_index),
+    (Ruling::Value(value), Some(scalar)) => Some(scalar.bucket(*value)),
+    (Ruling::Split(allocations), _) => allocations
+      .iter()
+      .rev()
+      .max_by_key(|allocation| allocation.share)
+      .map(|allocation| allocation.choice),
+    (Ruling::Ranking(ranking), _) => ranking.first().copied(),
+    _ => None,
+  };
+  juror.ruling = Some(decision.ruling.clone());
+  // inline logs are stored apart from the juror
+  let mut logs = decision.logs;
+  match logs.inline.take() {
+    Some(inline) => JUROR_LOGS.save(storage, decision.juror.clone(), &inline)?,
+    None => JUROR_LOGS.remove(storage, decision.juror.clone()),
+  }
+  let event = events::decide(
+    &decision.juror,
+    &decision.ruling,
+    &logs.hash,
+    &decision.script_hash,
+  )?;
+  juror.logs = Some(logs);
+
+  JURORS.save(storage, decision.juror.clone(), &juror)?;
+  append_history(
+    storage,
+    env,
+    &decision.juror,
+    HistoryAction::Decide {
+      ruling: decision.ruling,
+    },
+  )?;
+  Ok(event)
+}
+
+/// Logs must have a well-formed hash and be either stored inline, within the
+/// trial's limit, or referenced by URI. Inline logs must match their hash and
+/// length.
+fn validate_logs(
+  trial: &Trial,
+  logs: &Logs,
+) -> Result<(), ContractError> {
+  if !is_sha256_hex(&logs.hash) {
+    return Err(ContractError::InvalidLogs {});
+  }
+  match &logs.inline {
+    Some(inline) => {
+      let max_inline_logs = trial
+        .verdict
+        .max_inline_logs
+        .unwrap_or(DEFAULT_MAX_INLINE_LOGS);
+      if inline.len() > max_inline_logs as usize {
+        return Err(ContractError::LogsTooLarge {});
+      }
+      if inline.len() as u64 != logs.len || hash_logs(inline) != logs.hash {
+        return Err(ContractError::InvalidLogs {});
+      }
+    },
+    None if logs.uri.is_none() => return Err(ContractError::InvalidLogs {}),
+    None => {},
+  }
+  Ok(())
+}
+
+/// Allocations must name existing choices, sorted and without duplicates, with
+/// positive shares that sum to 1.
+fn validate_allocations(
+  trial: &Trial,
+  allocations: &[Allocation],
+) -> Result<(), ContractError> {
+  if allocations.is_empty() {
+    return Err(ContractError::InvalidAllocations {});
+  }
+  let mut total = Decimal::zero();
+  for (i, allocation) in allocations.iter().enumerate() {
+    if allocation.choice as usize >= trial.choices.len() {
+      return Err(ContractError::InvalidChoice {});
     }
-    Ok(response)
-  } else {
-    // the tx sender isn't a registered juror
-    return Err(ContractError::NotAuthorized {});
+    if allocation.share.is_zero() || (i > 0 && allocations[i - 1].choice == allocation.choice) {
+      return Err(ContractError::InvalidAllocations {});
+    }
+    total += allocation.share;
+  }
+  if total != Decimal::one() {
+    return Err(ContractError::InvalidAllocations {});
   }
+  Ok(())
 }
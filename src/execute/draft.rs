@@ -0,0 +1,131 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::{CancelPolicy, HistoryAction, Status, Trial, Verdict},
+  msg::{JurorIntiatiationParams, JuryPool},
+  state::{
+    append_history, save_config_hash, save_jury, set_choices, set_verdict, transition,
+    validate_trial, SCRIPT_BODY, TRIAL,
+  },
+  util::merge_response,
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, Storage};
+
+/// Load the trial for configuring, which only its owner can do, and only while
+/// it's still a draft.
+fn load_draft(
+  storage: &dyn Storage,
+  info: &MessageInfo,
+) -> Result<Trial, ContractError> {
+  let trial = TRIAL.load(storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if !trial.is_draft() {
+    return Err(ContractError::NotDraft {});
+  }
+  Ok(trial)
+}
+
+fn configured(field: &str) -> Response {
+  Response::new()
+    .add_attributes(vec![attr("action", "configure"), attr("field", field)])
+    .add_event(events::configure(field))
+}
+
+/// Replace the draft trial's choices.
+pub fn configure_choices(
+  deps: DepsMut,
+  info: MessageInfo,
+  choices: Vec<String>,
+) -> Result<Response, ContractError> {
+  let mut trial = load_draft(deps.storage, &info)?;
+  set_choices(&mut trial, &choices);
+  TRIAL.save(deps.storage, &trial)?;
+  Ok(configured("choices"))
+}
+
+/// Replace the draft trial's jury, or the pool from which it's drawn.
+pub fn configure_jury(
+  deps: DepsMut,
+  info: MessageInfo,
+  jury: Vec<JurorIntiatiationParams>,
+  jury_pool: Option<JuryPool>,
+) -> Result<Response, ContractError> {
+  load_draft(deps.storage, &info)?;
+  save_jury(deps.storage, &jury, jury_pool.as_ref())?;
+  Ok(configured("jury"))
+}
+
+/// Replace the draft trial's limits on cancellation by its owner.
+pub fn configure_cancel_policy(
+  deps: DepsMut,
+  info: MessageInfo,
+  cancel_policy: CancelPolicy,
+) -> Result<Response, ContractError> {
+  let mut trial = load_draft(deps.storage, &info)?;
+  trial.cancel_policy = cancel_policy;
+  TRIAL.save(deps.storage, &trial)?;
+  Ok(configured("cancel_policy"))
+}
+
+/// Replace the draft trial's verdict, including its script. A script too large
+/// for a single tx can be given in part, and the rest appended by
+/// `append_script`.
+pub fn configure_verdict(
+  deps: DepsMut,
+  info: MessageInfo,
+  verdict: Verdict,
+) -> Result<Response, ContractError> {
+  let mut trial = load_draft(deps.storage, &info)?;
+  set_verdict(deps.storage, &mut trial, &verdict)?;
+  TRIAL.save(deps.storage, &trial)?;
+  Ok(configured("verdict"))
+}
+
+/// Append a chunk to the draft trial's inline script.
+pub fn append_script(
+  deps: DepsMut,
+  info: MessageInfo,
+  chunk: &str,
+) -> Result<Response, ContractError> {
+  load_draft(deps.storage, &info)?;
+  let body = SCRIPT_BODY.may_load(deps.storage)?.unwrap_or_default() + chunk;
+  SCRIPT_BODY.save(deps.storage, &body)?;
+  Ok(configured("script"))
+}
+
+/// Validate the draft trial in full and open it for voting. The digest of the
+/// trial's configuration is kept, so that voters can check that it's the one
+/// they expect.
+pub fn publish(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut trial = load_draft(deps.storage, &info)?;
+  validate_trial(deps.as_ref(), &env, &trial)?;
+
+  let response = transition(deps.storage, &env, &info.sender, &mut trial, Status::Active)?;
+  TRIAL.save(deps.storage, &trial)?;
+
+  let config_hash = save_config_hash(deps.storage, &trial)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Publish {
+      config_hash: config_hash.clone(),
+    },
+  )?;
+
+  Ok(merge_response(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "publish"),
+        attr("config_hash", config_hash.clone()),
+      ])
+      .add_event(events::publish(&config_hash)),
+    response,
+  ))
+}
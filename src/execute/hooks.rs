@@ -0,0 +1,83 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::HistoryAction,
+  state::{append_history, HOOKS, MAX_HOOKS, TRIAL},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Register a contract to be notified of every change in the trial's status.
+/// Only the trial's owner can register hooks, up to `MAX_HOOKS` of them.
+pub fn add_hook(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  addr: &str,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let hook = deps.api.addr_validate(addr)?;
+  let mut hooks = HOOKS.load(deps.storage)?;
+  if hooks.contains(&hook) {
+    return Err(ContractError::HookAlreadyRegistered {});
+  }
+  if hooks.len() >= MAX_HOOKS {
+    return Err(ContractError::TooManyHooks {});
+  }
+  hooks.push(hook.clone());
+  HOOKS.save(deps.storage, &hooks)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::AddHook { hook: hook.clone() },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "add_hook"),
+        attr("hook", hook.to_string()),
+      ])
+      .add_event(events::add_hook(&hook)),
+  )
+}
+
+/// Unregister a hook contract. Only the trial's owner can remove hooks.
+pub fn remove_hook(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  addr: &str,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let hook = deps.api.addr_validate(addr)?;
+  let mut hooks = HOOKS.load(deps.storage)?;
+  if !hooks.contains(&hook) {
+    return Err(ContractError::HookNotRegistered {});
+  }
+  hooks.retain(|registered| *registered != hook);
+  HOOKS.save(deps.storage, &hooks)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::RemoveHook { hook: hook.clone() },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "remove_hook"),
+        attr("hook", hook.to_string()),
+      ])
+      .add_event(events::remove_hook(&hook)),
+  )
+}
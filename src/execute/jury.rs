@@ -0,0 +1,100 @@
+use crate::{
+  drand,
+  error::ContractError,
+  events,
+  models::{Beacon, HistoryAction, Status},
+  state::{append_history, save_juror, transition, BEACON, JURY_POOL, TRIAL},
+};
+use cosmwasm_std::{attr, Binary, DepsMut, Env, MessageInfo, Response};
+
+/// Select the trial's panel of jurors from its jury pool, once voting has
+/// closed. Anyone can submit the drand beacon for the first round published
+/// after voting closed, which the contract verifies against the network's
+/// public key. The beacon's randomness then determines the panel, so neither
+/// the trial's creator nor the submitter can choose the jurors.
+pub fn submit_beacon(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  round: u64,
+  signature: Binary,
+  previous_signature: Binary,
+) -> Result<Response, ContractError> {
+  let mut trial = TRIAL.load(deps.storage)?;
+  let jury_pool = JURY_POOL
+    .may_load(deps.storage)?
+    .ok_or(ContractError::NoJuryPool {})?;
+
+  // deliberations begin once voting has closed
+  let mut response = Response::new();
+  if trial.is_active() && trial.is_voting_closed(env.block.time) {
+    response = transition(
+      deps.storage,
+      &env,
+      &info.sender,
+      &mut trial,
+      Status::Deliberating,
+    )?;
+  }
+  // abort if we're not in the deciding state
+  if !trial.is_in_deliberations() {
+    return Err(ContractError::NotDeciding {});
+  }
+  // abort if the deliberation period has ended
+  if trial.is_deliberation_expired(env.block.time) {
+    return Err(ContractError::DeliberationsExpired {});
+  }
+  if BEACON.may_load(deps.storage)?.is_some() {
+    return Err(ContractError::PanelAlreadySelected {});
+  }
+
+  // only the first round after voting closed is accepted, since its
+  // randomness was unknown while votes were being cast
+  if round != jury_pool.drand.round_after(trial.verdict.start)
+    || !drand::verify(
+      &jury_pool.drand.pubkey,
+      round,
+      &previous_signature,
+      &signature,
+    )
+  {
+    return Err(ContractError::InvalidBeacon {});
+  }
+
+  let randomness = drand::randomness(&signature);
+  let mut panel: Vec<String> = Vec::with_capacity(jury_pool.panel_size as usize);
+  for i in drand::draw(
+    &randomness,
+    jury_pool.candidates.len(),
+    jury_pool.panel_size as usize,
+  ) {
+    let candidate = &jury_pool.candidates[i];
+    save_juror(deps.storage, candidate)?;
+    panel.push(candidate.address.to_string());
+  }
+
+  BEACON.save(
+    deps.storage,
+    &Beacon {
+      round,
+      randomness: randomness.into(),
+    },
+  )?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::SelectPanel { round },
+  )?;
+  TRIAL.save(deps.storage, &trial)?;
+
+  Ok(
+    response
+      .add_attributes(vec![
+        attr("action", "submit_beacon"),
+        attr("round", round.to_string()),
+        attr("panel", panel.join(",")),
+      ])
+      .add_event(events::select_panel(round, &panel)),
+  )
+}
@@ -1,9 +1,31 @@
+mod attest;
 mod cancel;
 mod claim;
 mod decide;
+mod draft;
+mod hooks;
+mod jury;
+mod ownership;
+mod pause;
+mod provider;
+mod resolve;
+mod update;
+mod verdict;
 mod vote;
 
-pub use cancel::cancel;
+pub use attest::submit_attestations;
+pub use cancel::{cancel, cosign_cancel};
 pub use claim::claim;
-pub use decide::decide;
+pub use decide::{decide, decide_invalid, decide_ranking, decide_split, decide_value};
+pub use draft::{
+  append_script, configure_cancel_policy, configure_choices, configure_jury, configure_verdict,
+  publish,
+};
+pub use hooks::{add_hook, remove_hook};
+pub use jury::submit_beacon;
+pub use ownership::{accept_ownership, propose_owner, renounce_ownership};
+pub use pause::{pause, set_guardian, unpause};
+pub use provider::submit_signed_result;
+pub use resolve::resolve;
+pub use update::update_trial;
 pub use vote::vote;
@@ -0,0 +1,123 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::{HistoryAction, PendingOwner},
+  state::{append_history, PENDING_OWNER, TRIAL},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+use cw_utils::Expiration;
+
+/// Propose a new owner for the trial, replacing any earlier proposal. Only the
+/// current owner can propose one, and ownership only changes once the proposed
+/// owner accepts it, which must happen before the proposal expires.
+pub fn propose_owner(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  new_owner: &str,
+  expiry: Option<Expiration>,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  let new_owner = deps.api.addr_validate(new_owner)?;
+  let expiry = expiry.unwrap_or(Expiration::Never {});
+  if expiry.is_expired(&env.block) {
+    return Err(ContractError::OwnershipProposalExpired {});
+  }
+
+  PENDING_OWNER.save(
+    deps.storage,
+    &PendingOwner {
+      owner: new_owner.clone(),
+      expiry,
+    },
+  )?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::ProposeOwner {
+      new_owner: new_owner.clone(),
+    },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "propose_owner"),
+        attr("new_owner", new_owner.to_string()),
+      ])
+      .add_event(events::propose_owner(&info.sender, &new_owner, &expiry)),
+  )
+}
+
+/// Accept ownership of the trial, as its proposed owner.
+pub fn accept_ownership(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut trial = TRIAL.load(deps.storage)?;
+  let pending = PENDING_OWNER
+    .may_load(deps.storage)?
+    .ok_or(ContractError::NoPendingOwner {})?;
+  if info.sender != pending.owner {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if pending.expiry.is_expired(&env.block) {
+    return Err(ContractError::OwnershipProposalExpired {});
+  }
+
+  let previous_owner = trial.owner.replace(info.sender.clone());
+  TRIAL.save(deps.storage, &trial)?;
+  PENDING_OWNER.remove(deps.storage);
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::AcceptOwnership {},
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "accept_ownership"),
+        attr("owner", info.sender.to_string()),
+      ])
+      .add_event(events::accept_ownership(
+        previous_owner.as_ref(),
+        &info.sender,
+      )),
+  )
+}
+
+/// Give up ownership of the trial for good, along with any pending proposal.
+/// Owner-only actions can no longer be taken.
+pub fn renounce_ownership(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  trial.owner = None;
+  TRIAL.save(deps.storage, &trial)?;
+  PENDING_OWNER.remove(deps.storage);
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::RenounceOwnership {},
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![attr("action", "renounce_ownership")])
+      .add_event(events::renounce_ownership(&info.sender)),
+  )
+}
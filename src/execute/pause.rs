@@ -0,0 +1,171 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::{HistoryAction, PausableOperation, Pause},
+  state::{append_history, GUARDIAN, MAX_PAUSE_SECONDS, PAUSES, TRIAL},
+};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, Storage};
+
+/// Only the trial's owner and its guardian, if any, can pause and unpause.
+fn ensure_owner_or_guardian(
+  storage: &dyn Storage,
+  sender: &Addr,
+) -> Result<(), ContractError> {
+  let trial = TRIAL.load(storage)?;
+  if trial.is_owner(sender) || GUARDIAN.may_load(storage)?.as_ref() == Some(sender) {
+    Ok(())
+  } else {
+    Err(ContractError::NotAuthorized {})
+  }
+}
+
+/// Keep the deliberation period extended by the time for which decisions have
+/// been paused.
+fn extend_deliberations(
+  storage: &mut dyn Storage,
+  operation: PausableOperation,
+  seconds_used: u64,
+) -> Result<(), ContractError> {
+  if operation == PausableOperation::Decide {
+    let mut trial = TRIAL.load(storage)?;
+    trial.decide_paused_seconds = seconds_used;
+    TRIAL.save(storage, &trial)?;
+  }
+  Ok(())
+}
+
+/// Appoint a guardian, who can pause and unpause operations alongside the
+/// owner, or remove the current one. Only the trial's owner can do so.
+pub fn set_guardian(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  guardian: Option<String>,
+) -> Result<Response, ContractError> {
+  let trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let guardian = guardian
+    .map(|guardian| deps.api.addr_validate(&guardian))
+    .transpose()?;
+  match &guardian {
+    Some(guardian) => GUARDIAN.save(deps.storage, guardian)?,
+    None => GUARDIAN.remove(deps.storage),
+  }
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::SetGuardian {
+      guardian: guardian.clone(),
+    },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![attr("action", "set_guardian")])
+      .add_event(events::set_guardian(guardian.as_ref())),
+  )
+}
+
+/// Pause an operation for the given number of seconds, e.g. when a bug is
+/// found, replacing any pause already in effect. An operation can only be
+/// paused for `MAX_PAUSE_SECONDS` in total, so that funds can't be held
+/// hostage. Pausing decisions extends the deliberation period by as long, and
+/// isn't possible once it has expired.
+pub fn pause(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  operation: PausableOperation,
+  seconds: u64,
+) -> Result<Response, ContractError> {
+  ensure_owner_or_guardian(deps.storage, &info.sender)?;
+  if seconds == 0 {
+    return Err(ContractError::ValidationError {});
+  }
+  let now = env.block.time;
+  if operation == PausableOperation::Decide
+    && TRIAL.load(deps.storage)?.is_deliberation_expired(now)
+  {
+    return Err(ContractError::DeliberationsExpired {});
+  }
+
+  // the rest of a pause already in effect is replaced, so isn't counted
+  let seconds_used = PAUSES
+    .may_load(deps.storage, operation.to_string())?
+    .map_or(0, |pause| {
+      pause.seconds_used - pause.until.seconds().saturating_sub(now.seconds())
+    });
+  if seconds_used + seconds > MAX_PAUSE_SECONDS {
+    return Err(ContractError::PauseLimitExceeded {
+      operation,
+      seconds_left: MAX_PAUSE_SECONDS - seconds_used,
+    });
+  }
+
+  let until = now.plus_seconds(seconds);
+  PAUSES.save(
+    deps.storage,
+    operation.to_string(),
+    &Pause {
+      until,
+      seconds_used: seconds_used + seconds,
+    },
+  )?;
+  extend_deliberations(deps.storage, operation, seconds_used + seconds)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Pause { operation, until },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "pause"),
+        attr("operation", operation.to_string()),
+        attr("until", until.to_string()),
+      ])
+      .add_event(events::pause(operation, until, &info.sender)),
+  )
+}
+
+/// Resume a paused operation before its pause ends. The rest of the pause
+/// isn't counted against the operation's limit.
+pub fn unpause(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  operation: PausableOperation,
+) -> Result<Response, ContractError> {
+  ensure_owner_or_guardian(deps.storage, &info.sender)?;
+
+  let now = env.block.time;
+  let mut pause = PAUSES
+    .may_load(deps.storage, operation.to_string())?
+    .filter(|pause| now < pause.until)
+    .ok_or(ContractError::NotPaused {})?;
+  pause.seconds_used -= pause.until.seconds() - now.seconds();
+  pause.until = now;
+  PAUSES.save(deps.storage, operation.to_string(), &pause)?;
+  extend_deliberations(deps.storage, operation, pause.seconds_used)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Unpause { operation },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![
+        attr("action", "unpause"),
+        attr("operation", operation.to_string()),
+      ])
+      .add_event(events::unpause(operation, &info.sender)),
+  )
+}
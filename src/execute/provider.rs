@@ -0,0 +1,110 @@
+use crate::{
+  error::ContractError,
+  events,
+  execute::{
+    decide::validate_ruling,
+    verdict::{render_invalid_verdict, render_verdict, ruling_allocations},
+  },
+  models::{HistoryAction, PausableOperation, ProviderResult, Status},
+  state::{append_history, ensure_not_paused, transition, TRIAL},
+  util::merge_response,
+};
+use cosmwasm_std::{attr, from_slice, Binary, DepsMut, Env, MessageInfo, Response};
+
+/// Finalize a trial with a result signed by its off-chain data provider. Anyone
+/// can relay the result once voting has closed. The payload is a JSON-encoded
+/// `ProviderResult`, signed as-is with the provider's ed25519 key, and must
+/// name this trial and a time after voting closed.
+pub fn submit_signed_result(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  payload: Binary,
+  signature: Binary,
+) -> Result<Response, ContractError> {
+  ensure_not_paused(deps.storage, &env, PausableOperation::Decide)?;
+  let mut trial = TRIAL.load(deps.storage)?;
+  let provider = trial
+    .verdict
+    .provider
+    .clone()
+    .ok_or(ContractError::NoProvider {})?;
+
+  // deliberations begin once voting has closed
+  let mut response = Response::new();
+  if trial.is_active() && trial.is_voting_closed(env.block.time) {
+    response = transition(
+      deps.storage,
+      &env,
+      &info.sender,
+      &mut trial,
+      Status::Deliberating,
+    )?;
+  }
+  // abort if we're not in the deciding state
+  if !trial.is_in_deliberations() {
+    return Err(ContractError::NotDeciding {});
+  }
+  // abort if the deliberation period has ended
+  if trial.is_deliberation_expired(env.block.time) {
+    return Err(ContractError::DeliberationsExpired {});
+  }
+
+  let is_valid = deps
+    .api
+    .ed25519_verify(&payload, &signature, &provider.pubkey)
+    .map_err(|_| ContractError::InvalidSignature {})?;
+  if !is_valid {
+    return Err(ContractError::InvalidSignature {});
+  }
+
+  // the result must be meant for this trial and produced after voting closed,
+  // so that results signed for other trials or in advance can't be replayed
+  let result: ProviderResult =
+    from_slice(&payload).map_err(|_| ContractError::InvalidPayload {})?;
+  if result.trial != env.contract.address || result.timestamp <= trial.verdict.start {
+    return Err(ContractError::InvalidPayload {});
+  }
+
+  let ruling = validate_ruling(&trial, result.ruling)?;
+  let event = events::signed_result(result.timestamp, &ruling)?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Decide {
+      ruling: ruling.clone(),
+    },
+  )?;
+
+  // provider-resolved trials have no jury to pay
+  let verdict_response = match ruling_allocations(&trial, ruling)? {
+    Some(allocations) => render_verdict(
+      deps.storage,
+      &env,
+      &info.sender,
+      &mut trial,
+      allocations,
+      &[],
+    )?,
+    None => render_invalid_verdict(deps.storage, &env, &info.sender, &mut trial, &[])?,
+  };
+  response = merge_response(response, verdict_response);
+
+  TRIAL.save(deps.storage, &trial)?;
+
+  Ok(
+    response
+      .add_attributes(vec![
+        attr("action", "submit_signed_result"),
+        attr("timestamp", result.timestamp.to_string()),
+        attr(
+          "winner",
+          trial
+            .winner
+            .map_or_else(|| "none".to_owned(), |winner| winner.to_string()),
+        ),
+      ])
+      .add_event(event),
+  )
+}
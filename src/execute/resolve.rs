@@ -0,0 +1,105 @@
+use crate::{
+  error::ContractError,
+  events,
+  execute::verdict::{render_verdict, ruling_allocations},
+  models::{HistoryAction, PausableOperation, Ruling, Status},
+  msg::OracleValueResponse,
+  state::{append_history, ensure_not_paused, transition, TRIAL},
+  util::merge_response,
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, QueryRequest, Response, WasmQuery};
+
+/// Resolve a trial through its oracle contract rather than a jury. Anyone can
+/// execute this method once voting has closed. The oracle is queried for a
+/// numeric value, which the trial's rules map to the winning choice, putting
+/// the game into the `HasVerdict` state.
+pub fn resolve(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  ensure_not_paused(deps.storage, &env, PausableOperation::Decide)?;
+  let mut trial = TRIAL.load(deps.storage)?;
+  let oracle = trial
+    .verdict
+    .oracle
+    .clone()
+    .ok_or(ContractError::NoOracle {})?;
+
+  // deliberations begin once voting has closed
+  let mut response = Response::new();
+  if trial.is_active() && trial.is_voting_closed(env.block.time) {
+    response = transition(
+      deps.storage,
+      &env,
+      &info.sender,
+      &mut trial,
+      Status::Deliberating,
+    )?;
+  }
+  // abort if we're not in the deciding state
+  if !trial.is_in_deliberations() {
+    return Err(ContractError::NotDeciding {});
+  }
+  // abort if the deliberation period has ended
+  if trial.is_deliberation_expired(env.block.time) {
+    return Err(ContractError::DeliberationsExpired {});
+  }
+
+  // ask the oracle for its value and map it to the winning choice. the query
+  // is already serialized, so it's forwarded as a raw smart query.
+  let resp: OracleValueResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+    contract_addr: oracle.address.to_string(),
+    msg: oracle.query.clone(),
+  }))?;
+  // in scalar trials, the value is mapped by the scalar ranges; otherwise, by
+  // the first matching rule.
+  let ruling = if trial.verdict.scalar.is_some() {
+    Ruling::Value(resp.value)
+  } else {
+    Ruling::Choice(
+      oracle
+        .rules
+        .iter()
+        .find(|rule| rule.matches(resp.value))
+        .map(|rule| rule.choice)
+        .ok_or(ContractError::NoMatchingOracleRule {})?,
+    )
+  };
+
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Decide {
+      ruling: ruling.clone(),
+    },
+  )?;
+  let allocations = ruling_allocations(&trial, ruling)?.ok_or(ContractError::NotDeciding {})?;
+
+  // oracle-resolved trials have no jury to pay
+  response = merge_response(
+    response,
+    render_verdict(
+      deps.storage,
+      &env,
+      &info.sender,
+      &mut trial,
+      allocations,
+      &[],
+    )?,
+  );
+
+  TRIAL.save(deps.storage, &trial)?;
+
+  Ok(
+    response
+      .add_attributes(vec![
+        attr("action", "resolve"),
+        attr("oracle", oracle.address.to_string()),
+        attr("value", resp.value.to_string()),
+        attr("winner", trial.winner.unwrap_or_default().to_string()),
+      ])
+      .add_event(events::resolve(&oracle.address, resp.value)),
+  )
+}
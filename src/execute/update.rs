@@ -0,0 +1,75 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::{HistoryAction, Style},
+  state::{append_history, save_config_hash, STATS, TRIAL},
+};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Edit the trial's prompt, choice texts or style, e.g. to fix a typo. Only the
+/// trial's owner can edit it, and only while it's a draft, or active but before
+/// any vote is cast, after which it's frozen for good. Each edit is recorded in
+/// the audit log and in an event, so that voters can verify what they voted on.
+/// Once published, the trial's config hash is recomputed to match the edit.
+pub fn update_trial(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  prompt: Option<String>,
+  choice_texts: Option<Vec<String>>,
+  style: Option<Style>,
+) -> Result<Response, ContractError> {
+  let mut trial = TRIAL.load(deps.storage)?;
+  if !trial.is_owner(&info.sender) {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if !(trial.is_draft() || trial.is_active()) || STATS.load(deps.storage)?.vote_count > 0 {
+    return Err(ContractError::TrialFrozen {});
+  }
+
+  if let Some(prompt) = &prompt {
+    trial.prompt = prompt.clone();
+  }
+  // choices can be renamed but not added or removed, since the verdict's rules
+  // refer to them by index
+  if let Some(choice_texts) = &choice_texts {
+    if choice_texts.len() != trial.choices.len() {
+      return Err(ContractError::ValidationError {});
+    }
+    for (choice, text) in trial.choices.iter_mut().zip(choice_texts) {
+      choice.text = text.clone();
+    }
+  }
+  if let Some(style) = &style {
+    trial.style = style.clone();
+  }
+  TRIAL.save(deps.storage, &trial)?;
+  let config_hash = if trial.is_draft() {
+    None
+  } else {
+    Some(save_config_hash(deps.storage, &trial)?)
+  };
+
+  let event = events::update_trial(
+    prompt.as_deref(),
+    choice_texts.as_deref(),
+    style.as_ref(),
+    config_hash.as_deref(),
+  )?;
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::UpdateTrial {
+      prompt,
+      choice_texts,
+      style,
+    },
+  )?;
+
+  Ok(
+    Response::new()
+      .add_attributes(vec![attr("action", "update_trial")])
+      .add_event(event),
+  )
+}
@@ -0,0 +1,180 @@
+use crate::{
+  error::ContractError,
+  events,
+  models::{Allocation, Juror, Ruling, Status, Token, Trial},
+  state::{transition, ALLOCATIONS, DEFAULT_INVALID_FEE_PCT, STATS, VOTERS_TOTAL_CLAIM_AMOUNT},
+  util::{build_cw20_transfer_msg, build_native_send_msg, merge_response},
+};
+use cosmwasm_std::{Addr, Decimal, Env, Response, Storage, SubMsg, Uint128};
+
+/// Map a ruling to the shares of the pot allocated to each winning choice, or
+/// `None` if the ruling is that the trial is invalid.
+pub fn ruling_allocations(
+  trial: &Trial,
+  ruling: Ruling,
+) -> Result<Option<Vec<Allocation>>, ContractError> {
+  Ok(match ruling {
+    Ruling::Choice(choice_index) => Some(vec![Allocation::whole(choice_index)]),
+    Ruling::Value(value) => Some(
+      trial
+        .verdict
+        .scalar
+        .as_ref()
+        .ok_or(ContractError::NotScalar {})?
+        .allocations(value),
+    ),
+    Ruling::Split(allocations) => Some(allocations),
+    // each placed choice is allocated its place's percent of the pot
+    Ruling::Ranking(ranking) => Some(
+      ranking
+        .iter()
+        .zip(
+          trial
+            .verdict
+            .payout_table
+            .as_ref()
+            .ok_or(ContractError::NotRanked {})?,
+        )
+        .map(|(choice_index, pct)| Allocation {
+          choice: *choice_index,
+          share: Decimal::percent(*pct as u64),
+        })
+        .collect(),
+    ),
+    Ruling::Invalid => None,
+  })
+}
+
+/// Put the trial into the `HasVerdict` state, allocating shares of the pot to
+/// one or more winning choices. The choice with the largest share is recorded
+/// as the trial's winner. Each juror is paid their fee from the pot, and the
+/// remainder is reserved for claims by winning voters. Returns a partial
+/// response with the verdict's events and the msgs for notifying hooks and
+/// transferring fees to the jury. The trial must still be saved by the caller.
+pub fn render_verdict(
+  storage: &mut dyn Storage,
+  env: &Env,
+  actor: &Addr,
+  trial: &mut Trial,
+  allocations: Vec<Allocation>,
+  jurors: &[Juror],
+) -> Result<Response, ContractError> {
+  // the choice with the largest share (the first one, in case of a tie)
+  trial.winner = allocations
+    .iter()
+    .rev()
+    .max_by_key(|allocation| allocation.share)
+    .map(|allocation| allocation.choice);
+  let response = transition(storage, env, actor, trial, Status::HasVerdict)?;
+
+  let (jury_response, jurors_total_claim_amount) = pay_jury(env, trial, jurors, 100)?;
+
+  // save the remainder of the rewards eligible for claims by winning voters
+  let voters_total_amount = trial.pot() - jurors_total_claim_amount;
+  VOTERS_TOTAL_CLAIM_AMOUNT.save(storage, &voters_total_amount)?;
+
+  // the voters who backed a winning choice are now owed their share of the
+  // remainder. a share allocated to a choice without any voters is unclaimable.
+  let mut claimants = 0u32;
+  let mut liabilities = Uint128::zero();
+  for allocation in allocations.iter() {
+    let voters = trial.choices[allocation.choice as usize].voters;
+    if voters > 0 {
+      claimants += voters;
+      liabilities += voters_total_amount * allocation.share;
+    }
+  }
+  STATS.update(storage, |mut stats| -> Result<_, ContractError> {
+    stats.juror_fees_paid = jurors_total_claim_amount;
+    stats.claimants_remaining = claimants;
+    stats.liabilities = liabilities;
+    Ok(stats)
+  })?;
+  ALLOCATIONS.save(storage, &allocations)?;
+
+  Ok(
+    merge_response(response, jury_response).add_event(events::verdict(
+      trial.winner,
+      &allocations,
+      voters_total_amount,
+      jurors_total_claim_amount,
+    )?),
+  )
+}
+
+/// Put the trial into the `Invalid` state, where voters are refunded in
+/// proportion to their weight. Jurors are still paid a reduced fee, so that
+/// ruling a trial invalid isn't penalized, and the remainder of the pot is
+/// reserved for refunds. Returns a partial response with the verdict's events
+/// and the msgs for notifying hooks and transferring fees to the jury. The
+/// trial must still be saved by the caller.
+pub fn render_invalid_verdict(
+  storage: &mut dyn Storage,
+  env: &Env,
+  actor: &Addr,
+  trial: &mut Trial,
+  jurors: &[Juror],
+) -> Result<Response, ContractError> {
+  let response = transition(storage, env, actor, trial, Status::Invalid)?;
+
+  let fee_pct = trial
+    .verdict
+    .invalid_fee_pct
+    .unwrap_or(DEFAULT_INVALID_FEE_PCT);
+  let (jury_response, jurors_total_claim_amount) = pay_jury(env, trial, jurors, fee_pct)?;
+
+  // save the remainder of the pot to be refunded to voters
+  let voters_total_amount = trial.pot() - jurors_total_claim_amount;
+  VOTERS_TOTAL_CLAIM_AMOUNT.save(storage, &voters_total_amount)?;
+
+  // every voter is now owed a refund
+  STATS.update(storage, |mut stats| -> Result<_, ContractError> {
+    stats.juror_fees_paid = jurors_total_claim_amount;
+    stats.claimants_remaining = stats.voter_count;
+    stats.liabilities = voters_total_amount;
+    Ok(stats)
+  })?;
+
+  Ok(
+    merge_response(response, jury_response).add_event(events::verdict(
+      None,
+      &[],
+      voters_total_amount,
+      jurors_total_claim_amount,
+    )?),
+  )
+}
+
+/// Build transfer msgs for auto-sending fees to jury members, where each juror
+/// receives `fee_pct` percent of their configured fee. Returns a partial
+/// response with the msgs and an event per fee, along with the total amount
+/// paid.
+fn pay_jury(
+  env: &Env,
+  trial: &Trial,
+  jurors: &[Juror],
+  fee_pct: u8,
+) -> Result<(Response, Uint128), ContractError> {
+  let total = trial.pot();
+  let mut jurors_total_claim_amount = Uint128::zero();
+  let mut response = Response::new();
+
+  if total > Uint128::zero() {
+    for juror in jurors.iter() {
+      let amount = total.multiply_ratio(juror.pct as u128 * fee_pct as u128, 100u128 * 100u128);
+      jurors_total_claim_amount += amount;
+      response = response
+        .add_submessage(match trial.token.clone() {
+          Token::Cw20 { address } => {
+            build_cw20_transfer_msg(&env.contract.address, &juror.address, &address, amount)?
+          },
+          Token::Native { denom } => {
+            SubMsg::new(build_native_send_msg(&juror.address, &denom, amount)?)
+          },
+        })
+        .add_event(events::juror_fee(&juror.address, amount));
+    }
+  }
+
+  Ok((response, jurors_total_claim_amount))
+}
@@ -1,12 +1,16 @@
 use crate::{
   error::ContractError,
-  models::{Token, Vote},
-  state::{TRIAL, VOTES},
+  events,
+  models::{HistoryAction, OddsSnapshot, PausableOperation, Token, Vote},
+  state::{
+    append_history, ensure_not_paused, CHOICE_VOTES_BY_WEIGHT, ODDS_HISTORY, ODDS_INTERVAL_MINUTES,
+    STATS, TRIAL, VOTER_WEIGHTS, VOTES, VOTES_BY_WEIGHT,
+  },
   util::{
     build_cw20_transfer_msg, build_native_send_msg, validate_cw20_funds, validate_native_funds,
   },
 };
-use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{attr, DepsMut, Empty, Env, MessageInfo, Response, Uint128};
 
 /// Cast a vote on an active trial. The funding required is proportional to the
 /// weight of the vote.
@@ -17,11 +21,12 @@ pub fn vote(
   choice_index: usize,
   weight: u32,
 ) -> Result<Response, ContractError> {
+  ensure_not_paused(deps.storage, &env, PausableOperation::Vote)?;
   let mut trial = TRIAL.load(deps.storage)?;
   let payment = Uint128::from(weight) * trial.price;
 
   // abort the vote if the trial is closed
-  if !trial.is_active() {
+  if !trial.is_active() || trial.is_voting_closed(env.block.time) {
     return Err(ContractError::NotActive {});
   }
   // abort if the weight is 0
@@ -36,7 +41,8 @@ pub fn vote(
     choice.weight += weight;
     // upsert the wallet's voting record for this choice,
     // incrementing the net weight.
-    VOTES.update(
+    let prev_vote = VOTES.may_load(deps.storage, (choice_index as u32, info.sender.clone()))?;
+    let vote = VOTES.update(
       deps.storage,
       (choice_index as u32, info.sender.clone()),
       |some_vote| -> Result<Vote, ContractError> {
@@ -51,6 +57,29 @@ pub fn vote(
         }
       },
     )?;
+    // move the position to its new place in the weight-sorted indices
+    if let Some(prev_vote) = prev_vote {
+      VOTES_BY_WEIGHT.remove(
+        deps.storage,
+        (prev_vote.weight, prev_vote.choice, info.sender.clone()),
+      );
+      CHOICE_VOTES_BY_WEIGHT.remove(
+        deps.storage,
+        (prev_vote.choice, prev_vote.weight, info.sender.clone()),
+      );
+    } else {
+      choice.voters += 1;
+    }
+    VOTES_BY_WEIGHT.save(
+      deps.storage,
+      (vote.weight, vote.choice, info.sender.clone()),
+      &Empty {},
+    )?;
+    CHOICE_VOTES_BY_WEIGHT.save(
+      deps.storage,
+      (vote.choice, vote.weight, info.sender.clone()),
+      &Empty {},
+    )?;
   } else {
     return Err(ContractError::InvalidChoice {});
   }
@@ -58,10 +87,55 @@ pub fn vote(
   // note: trial.weight must equal the sum of the choices' weights
   trial.weight += weight;
 
+  // update the wallet's net weight across all choices, as well as the
+  // aggregate stats, counting the wallet as a new voter on its first vote.
+  let is_new_voter = !VOTER_WEIGHTS.has(deps.storage, info.sender.clone());
+  VOTER_WEIGHTS.update(
+    deps.storage,
+    info.sender.clone(),
+    |some_weight| -> Result<u32, ContractError> { Ok(some_weight.unwrap_or(0) + weight) },
+  )?;
+  STATS.update(deps.storage, |mut stats| -> Result<_, ContractError> {
+    if is_new_voter {
+      stats.voter_count += 1;
+    }
+    stats.vote_count += 1;
+    stats.total_escrowed += payment;
+    Ok(stats)
+  })?;
+
   // save Ballot with updated Choice record
   TRIAL.save(deps.storage, &trial)?;
 
+  append_history(
+    deps.storage,
+    &env,
+    &info.sender,
+    HistoryAction::Vote {
+      choice: choice_index as u32,
+      weight,
+    },
+  )?;
+
+  // snapshot the weights of all choices in the current time bucket. Later
+  // votes in the same bucket overwrite the snapshot, so at most one is kept
+  // per interval, reflecting the odds as of the end of the interval.
+  let interval_seconds = 60u64 * (ODDS_INTERVAL_MINUTES.load(deps.storage)? as u64);
+  let now = env.block.time.seconds();
+  let bucket = now - now % interval_seconds;
+  ODDS_HISTORY.save(
+    deps.storage,
+    bucket,
+    &OddsSnapshot {
+      bucket,
+      time: env.block.time,
+      weight: trial.weight,
+      weights: trial.choices.iter().map(|choice| choice.weight).collect(),
+    },
+  )?;
+
   // return a respnse with the necessary transfer msg/submsg
+  let event = events::vote(&info.sender, choice_index as u32, weight, payment);
   Ok(match trial.token.clone() {
     Token::Native { denom } => {
       validate_native_funds(&info.funds, payment, &denom)?;
@@ -72,6 +146,7 @@ pub fn vote(
           payment,
         )?)
         .add_attributes(vec![attr("action", "vote")])
+        .add_event(event)
     },
     Token::Cw20 {
       address: cw20_token_address,
@@ -85,6 +160,7 @@ pub fn vote(
           payment,
         )?)
         .add_attributes(vec![attr("action", "vote")])
+        .add_event(event)
     },
   })
 }
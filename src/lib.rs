@@ -1,5 +1,7 @@
 pub mod contract;
+mod drand;
 mod error;
+pub mod events;
 pub mod execute;
 pub mod models;
 pub mod msg;
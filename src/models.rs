@@ -1,15 +1,37 @@
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Binary, Decimal, Timestamp, Uint128};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Status {
+  Draft,
   Active,
   Deliberating,
   HasVerdict,
   HungJury,
   Dismissed,
+  Invalid,
+}
+
+impl fmt::Display for Status {
+  /// Formats the status as it's serialized, e.g. `has_verdict`.
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter,
+  ) -> fmt::Result {
+    f.write_str(match self {
+      Status::Draft => "draft",
+      Status::Active => "active",
+      Status::Deliberating => "deliberating",
+      Status::HasVerdict => "has_verdict",
+      Status::HungJury => "hung_jury",
+      Status::Dismissed => "dismissed",
+      Status::Invalid => "invalid",
+    })
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -30,6 +52,7 @@ pub struct Choice {
   pub text: String,
   pub tally: u32,
   pub weight: u32,
+  pub voters: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -52,14 +75,278 @@ pub enum ProgrammingLanguage {
   TypeScript,
   Rust,
   Bash,
+  Go,
+  Wasm,
+}
+
+/// The form of a decision script's output, which must match how the trial is
+/// decided.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+  /// A winning choice index.
+  Choice,
+  /// A decimal value, for scalar trials.
+  Value,
+  /// A list of winning choices with their shares of the pot.
+  Allocations,
+  /// A list of choice indices from first place to last, for ranked trials.
+  Ranking,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EnvVar {
+  pub name: String,
+  pub value: String,
+}
+
+/// Pins the environment in which jurors run the decision script, so that they
+/// all get the same output.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Runtime {
+  /// The interpreter, compiler or VM version, e.g. "3.11.4" for Python.
+  pub version: String,
+  /// The lowercase hex-encoded sha256 digest of the dependency lockfile.
+  pub lockfile_hash: Option<String>,
+  pub args: Vec<String>,
+  pub env: Vec<EnvVar>,
+  pub output: OutputFormat,
+}
+
+/// Maps a numeric value reported by an oracle to a choice. `min` is inclusive
+/// and `max` is exclusive. A missing bound is unbounded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OracleRule {
+  pub choice: u32,
+  pub min: Option<Decimal>,
+  pub max: Option<Decimal>,
+}
+
+impl OracleRule {
+  pub fn matches(
+    &self,
+    value: Decimal,
+  ) -> bool {
+    self.min.map_or(true, |min| value >= min) && self.max.map_or(true, |max| value < max)
+  }
+}
+
+/// An oracle contract that resolves the trial in lieu of a jury. The `query`
+/// is sent as-is to the oracle contract, which must respond with a numeric
+/// value, mapped to the winning choice by the first matching rule.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Oracle {
+  pub address: Addr,
+  pub query: Binary,
+  pub rules: Vec<OracleRule>,
+}
+
+/// The range of values mapped to a choice in a scalar trial. `min` is
+/// inclusive and `max` is exclusive.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScalarRange {
+  pub min: Decimal,
+  pub max: Decimal,
+}
+
+impl ScalarRange {
+  pub fn midpoint(&self) -> Decimal {
+    self.min + (self.max - self.min) / Uint128::from(2u128)
+  }
+}
+
+/// Configuration for trials whose outcome is a number rather than a choice.
+/// Each choice has a range, in order, and the ranges must be contiguous. Values
+/// outside of the ranges are clamped to the first or last choice. If `linear`
+/// is set, the pot is split between the two choices whose midpoints surround
+/// the value, in proportion to its distance from each midpoint.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Scalar {
+  pub ranges: Vec<ScalarRange>,
+  pub linear: bool,
+}
+
+impl Scalar {
+  /// Return the index of the choice whose range contains the value.
+  pub fn bucket(
+    &self,
+    value: Decimal,
+  ) -> u32 {
+    self
+      .ranges
+      .iter()
+      .position(|range| value < range.max)
+      .unwrap_or(self.ranges.len() - 1) as u32
+  }
+
+  /// Return the share of the pot allocated to each winning choice.
+  pub fn allocations(
+    &self,
+    value: Decimal,
+  ) -> Vec<Allocation> {
+    if !self.linear {
+      return vec![Allocation::whole(self.bucket(value))];
+    }
+    let midpoints: Vec<Decimal> = self.ranges.iter().map(|range| range.midpoint()).collect();
+    // find the first midpoint above the value. the value lies between this
+    // midpoint and the previous one, if any.
+    match midpoints.iter().position(|midpoint| value < *midpoint) {
+      None => vec![Allocation::whole((midpoints.len() - 1) as u32)],
+      Some(0) => vec![Allocation::whole(0)],
+      Some(upper) => {
+        let lower = upper - 1;
+        let upper_share = (value - midpoints[lower]) / (midpoints[upper] - midpoints[lower]);
+        let mut allocations = vec![Allocation {
+          choice: lower as u32,
+          share: Decimal::one() - upper_share,
+        }];
+        if !upper_share.is_zero() {
+          allocations.push(Allocation {
+            choice: upper as u32,
+            share: upper_share,
+          });
+        }
+        allocations
+      },
+    }
+  }
+}
+
+/// The share of the pot to be split among voters for a winning choice.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Allocation {
+  pub choice: u32,
+  pub share: Decimal,
+}
+
+impl Allocation {
+  pub fn whole(choice: u32) -> Self {
+    Self {
+      choice,
+      share: Decimal::one(),
+    }
+  }
+}
+
+/// The output of a juror's script run, identified by the digest of its
+/// contents. Logs are stored inline, up to the trial's limit, or referenced by
+/// a URI where they can be fetched.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Logs {
+  /// The lowercase hex-encoded sha256 digest of the logs.
+  pub hash: String,
+  /// The length of the logs in bytes.
+  pub len: u64,
+  pub uri: Option<String>,
+  /// The logs themselves, if stored on-chain. They're kept apart from the
+  /// juror.
+  pub inline: Option<String>,
+}
+
+/// A juror's ruling, signed off-chain so that anyone can submit it on the
+/// juror's behalf.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Attestation {
+  pub juror: Addr,
+  pub ruling: Ruling,
+  pub logs: Logs,
+  pub script_hash: String,
+  /// A 64-byte secp256k1 signature over the sha256 digest of the JSON-encoded
+  /// `AttestationPayload`.
+  pub signature: Binary,
+}
+
+/// The data signed by a juror in an attestation. Each contract holds a single
+/// trial, so the contract address identifies the trial.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttestationPayload {
+  pub contract: Addr,
+  pub ruling: Ruling,
+  /// The hash of the juror's logs.
+  pub logs_hash: String,
+  /// The hash of the script run by the juror.
+  pub script_hash: String,
+}
+
+/// A drand network running the chained scheme, e.g. the League of Entropy's
+/// mainnet, whose beacons are signed on G2 by a public key on G1.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Drand {
+  /// The network's 48-byte compressed G1 public key.
+  pub pubkey: Binary,
+  pub genesis_time: Timestamp,
+  pub period_seconds: u64,
+}
+
+impl Drand {
+  /// The first round published at or after the given time. Round 1 is
+  /// published at genesis, with a new round every period.
+  pub fn round_after(
+    &self,
+    time: Timestamp,
+  ) -> u64 {
+    if time <= self.genesis_time {
+      return 1;
+    }
+    let elapsed = time.seconds() - self.genesis_time.seconds();
+    (elapsed + self.period_seconds - 1) / self.period_seconds + 1
+  }
+}
+
+/// A verified drand beacon.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Beacon {
+  pub round: u64,
+  /// The sha256 digest of the beacon's signature.
+  pub randomness: Binary,
+}
+
+/// An off-chain data provider, such as a sports or price feed, trusted to sign
+/// the trial's result in lieu of a jury.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Provider {
+  /// The provider's 32-byte ed25519 public key.
+  pub pubkey: Binary,
+}
+
+/// The result signed by a data provider.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProviderResult {
+  /// The address of the trial's contract.
+  pub trial: Addr,
+  /// When the result was produced, which must be after voting closed.
+  pub timestamp: Timestamp,
+  pub ruling: Ruling,
+}
+
+/// The decision script, addressed by the digest of its contents so that jurors
+/// can prove which script they ran.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Script {
+  /// The lowercase hex-encoded sha256 digest of the script.
+  pub hash: String,
+  /// Where the script can be fetched, e.g. an IPFS URI.
+  pub uri: Option<String>,
+  /// The script itself, if stored on-chain. It's kept apart from the trial.
+  pub body: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Verdict {
-  pub script: String,
+  pub script: Script,
   pub language: ProgrammingLanguage,
+  pub runtime: Option<Runtime>,
   pub start: Timestamp,
   pub minutes: u32,
+  pub oracle: Option<Oracle>,
+  pub scalar: Option<Scalar>,
+  pub invalid_fee_pct: Option<u8>,
+  /// In ranked trials, the percent of the pot paid to each place, from first to
+  /// last, e.g. `[60, 30, 10]` for a top-3 prompt.
+  pub payout_table: Option<Vec<u8>>,
+  pub provider: Option<Provider>,
+  /// The largest logs, in bytes, that jurors may store inline.
+  pub max_inline_logs: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -68,19 +355,251 @@ pub struct Style {
   pub font: Font,
 }
 
+/// The outcome reached by a juror, or by an oracle on behalf of the jury.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ruling {
+  /// The winning choice index.
+  Choice(u32),
+  /// The numeric outcome of a scalar trial.
+  Value(Decimal),
+  /// Several winning choices, each allocated a share of the pot.
+  Split(Vec<Allocation>),
+  /// The choices of a ranked trial, from first place to last.
+  Ranking(Vec<u32>),
+  /// The prompt can't be answered, so voters are refunded.
+  Invalid,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Juror {
   pub address: Addr,
   pub name: String,
   pub url: Option<String>,
   pub choice: Option<u32>,
-  pub logs: Option<String>,
+  pub ruling: Option<Ruling>,
+  pub logs: Option<Logs>,
   pub pct: u8,
+  /// The secp256k1 public key with which the juror signs attestations.
+  pub pubkey: Option<Binary>,
+}
+
+impl Juror {
+  pub fn has_decided(&self) -> bool {
+    self.ruling.is_some()
+  }
+
+  /// Jurors agree when they've reached the same ruling and their script runs
+  /// produced the same logs.
+  pub fn agrees_with(
+    &self,
+    other: &Juror,
+  ) -> bool {
+    self.ruling == other.ruling
+      && self.logs.as_ref().map(|logs| &logs.hash) == other.logs.as_ref().map(|logs| &logs.hash)
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Trial {
+pub struct Cancellation {
+  pub canceled_by: Addr,
+  pub canceled_at: Timestamp,
+  pub height: u64,
+  pub reason: String,
+  pub refundable: Uint128,
+  /// The bond posted by the owner, which is included in `refundable`.
+  #[serde(default)]
+  pub bond: Uint128,
+  /// The jurors who co-signed the cancellation.
+  #[serde(default)]
+  pub cosigners: Vec<Addr>,
+}
+
+/// Guardrails on the owner's ability to cancel the trial, so that it can't be
+/// dismissed just ahead of an unfavorable verdict.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CancelPolicy {
+  /// The amount of the trial's token the owner must post to cancel a trial
+  /// that has been voted on. The bond is paid out to voters along with their
+  /// refunds, in proportion to their weight.
+  pub bond: Uint128,
+  /// The percentage of jurors who must co-sign a cancellation once
+  /// deliberations have begun.
+  pub juror_quorum_pct: u8,
+}
+
+impl Default for CancelPolicy {
+  /// No bond, and a majority of jurors must co-sign.
+  fn default() -> Self {
+    CancelPolicy {
+      bond: Uint128::zero(),
+      juror_quorum_pct: 51,
+    }
+  }
+}
+
+impl CancelPolicy {
+  /// The number of co-signers required out of a jury of `n_jurors`. At least
+  /// one is always required, so a trial without jurors can't be canceled once
+  /// deliberations have begun, and instead hangs when they expire.
+  pub fn quorum(
+    &self,
+    n_jurors: u32,
+  ) -> u32 {
+    let pct = self.juror_quorum_pct as u32;
+    ((n_jurors * pct + 99) / 100).max(1)
+  }
+}
+
+/// The weights of all choices as of the last vote cast within a time bucket.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OddsSnapshot {
+  pub bucket: u64,
+  pub time: Timestamp,
+  pub weight: u32,
+  pub weights: Vec<u32>,
+}
+
+/// A compact record of a state-changing action, as stored in the audit log.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryAction {
+  Vote {
+    choice: u32,
+    weight: u32,
+  },
+  Decide {
+    ruling: Ruling,
+  },
+  Cancel {
+    reason: String,
+  },
+  CosignCancel {},
+  Claim {
+    amount: Uint128,
+    refund: bool,
+  },
+  Transition {
+    from: Status,
+    to: Status,
+  },
+  SelectPanel {
+    round: u64,
+  },
+  AddHook {
+    hook: Addr,
+  },
+  RemoveHook {
+    hook: Addr,
+  },
+  UpdateTrial {
+    prompt: Option<String>,
+    choice_texts: Option<Vec<String>>,
+    style: Option<Style>,
+  },
+  Publish {
+    config_hash: String,
+  },
+  ProposeOwner {
+    new_owner: Addr,
+  },
+  AcceptOwnership {},
+  RenounceOwnership {},
+  SetGuardian {
+    guardian: Option<Addr>,
+  },
+  Pause {
+    operation: PausableOperation,
+    until: Timestamp,
+  },
+  Unpause {
+    operation: PausableOperation,
+  },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HistoryEntry {
+  pub seq: u64,
+  pub actor: Addr,
+  pub height: u64,
+  pub time: Timestamp,
+  pub action: HistoryAction,
+}
+
+/// Running totals, kept up to date as votes are cast, the verdict is reached
+/// and claims are paid out.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Stats {
+  pub voter_count: u32,
+  pub vote_count: u32,
+  pub total_escrowed: Uint128,
+  pub total_claimed: Uint128,
+  pub liabilities: Uint128,
+  pub claimants_remaining: u32,
+  pub juror_fees_paid: Uint128,
+}
+
+/// An address proposed as the trial's next owner, which must accept ownership
+/// before the proposal expires.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
   pub owner: Addr,
+  pub expiry: Expiration,
+}
+
+/// An operation that the owner or guardian can pause in an emergency.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PausableOperation {
+  Vote,
+  Claim,
+  Decide,
+}
+
+impl PausableOperation {
+  pub const ALL: [PausableOperation; 3] = [
+    PausableOperation::Vote,
+    PausableOperation::Claim,
+    PausableOperation::Decide,
+  ];
+}
+
+impl fmt::Display for PausableOperation {
+  /// Formats the operation as it's serialized, e.g. `vote`.
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter,
+  ) -> fmt::Result {
+    f.write_str(match self {
+      PausableOperation::Vote => "vote",
+      PausableOperation::Claim => "claim",
+      PausableOperation::Decide => "decide",
+    })
+  }
+}
+
+/// When a paused operation resumes, along with how long it has been paused for
+/// in total, which is bounded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Pause {
+  pub until: Timestamp,
+  pub seconds_used: u64,
+}
+
+/// The trial's outcome, stored apart from the trial itself so that other
+/// contracts can condition on it without loading the whole trial.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Resolution {
+  pub resolved: bool,
+  pub winner: Option<u32>,
+  pub status: Status,
+  pub resolved_at: Option<Timestamp>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Trial {
+  /// `None` once ownership has been renounced.
+  pub owner: Option<Addr>,
   pub prompt: String,
   pub choices: Vec<Choice>,
   pub verdict: Verdict,
@@ -90,9 +609,52 @@ pub struct Trial {
   pub style: Style,
   pub weight: u32,
   pub winner: Option<u32>,
+  #[serde(default)]
+  pub cancel_policy: CancelPolicy,
+  /// Seconds for which decisions have been paused, by which the deliberation
+  /// period is extended.
+  #[serde(default)]
+  pub decide_paused_seconds: u64,
 }
 
 impl Trial {
+  /// The total amount paid in by voters.
+  pub fn pot(&self) -> Uint128 {
+    self.price * Uint128::from(self.weight)
+  }
+
+  /// Voting closes once deliberations are scheduled to begin.
+  pub fn is_voting_closed(
+    &self,
+    time: Timestamp,
+  ) -> bool {
+    time >= self.verdict.start
+  }
+
+  /// Jurors can no longer decide once the deliberation period has ended,
+  /// including any time for which decisions were paused.
+  pub fn is_deliberation_expired(
+    &self,
+    time: Timestamp,
+  ) -> bool {
+    time
+      > self
+        .verdict
+        .start
+        .plus_seconds(60u64 * (self.verdict.minutes as u64) + self.decide_paused_seconds)
+  }
+
+  pub fn is_owner(
+    &self,
+    addr: &Addr,
+  ) -> bool {
+    self.owner.as_ref() == Some(addr)
+  }
+
+  pub fn is_draft(&self) -> bool {
+    self.status == Status::Draft
+  }
+
   pub fn is_active(&self) -> bool {
     self.status == Status::Active
   }
@@ -113,7 +675,26 @@ impl Trial {
     self.status == Status::HungJury
   }
 
+  pub fn was_ruled_invalid(&self) -> bool {
+    self.status == Status::Invalid
+  }
+
+  /// Once out of the `Draft`, `Active` and `Deliberating` states, a trial's
+  /// outcome is final.
+  pub fn is_resolved(&self) -> bool {
+    !(self.is_draft() || self.is_active() || self.is_in_deliberations())
+  }
+
   pub fn can_be_canceled(&self) -> bool {
     self.status == Status::Active || self.status == Status::Deliberating
   }
+
+  /// Deliberations begin once voting has closed, even if the trial's status
+  /// has yet to be moved to `Deliberating`.
+  pub fn has_deliberations_begun(
+    &self,
+    time: Timestamp,
+  ) -> bool {
+    self.is_in_deliberations() || (self.is_active() && self.is_voting_closed(time))
+  }
 }
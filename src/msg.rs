@@ -1,5 +1,11 @@
-use crate::models::{Style, Token, Trial, Verdict};
-use cosmwasm_std::{Addr, Uint128};
+use crate::models::{
+  Allocation, Attestation, Beacon, CancelPolicy, Cancellation, Drand, HistoryEntry, Logs,
+  OddsSnapshot, PausableOperation, Script, Stats, Status, Style, Token, Trial, Verdict,
+};
+use cosmwasm_std::{
+  to_binary, Addr, Binary, CosmosMsg, Decimal, StdResult, Timestamp, Uint128, WasmMsg,
+};
+use cw_utils::Expiration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +15,7 @@ pub struct JurorIntiatiationParams {
   pub name: String,
   pub url: Option<String>,
   pub pct: u8,
+  pub pubkey: Option<Binary>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,16 +27,114 @@ pub struct InstantiateMsg {
   pub price: Uint128,
   pub style: Style,
   pub jury: Vec<JurorIntiatiationParams>,
+  pub odds_interval_minutes: Option<u32>,
+  pub jury_pool: Option<JuryPool>,
+  pub cancel_policy: Option<CancelPolicy>,
+}
+
+/// Candidates from which a panel of jurors is drawn at random once voting has
+/// closed, in lieu of a jury named upfront.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JuryPool {
+  pub candidates: Vec<JurorIntiatiationParams>,
+  pub panel_size: u32,
+  pub drand: Drand,
 }
 
 /// Executable contract endpoints.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-  Vote { choice: u32, weight: u32 },
-  Decide { choice: u32, logs: String },
-  Cancel { reason: String },
+  Vote {
+    choice: u32,
+    weight: u32,
+  },
+  Decide {
+    choice: u32,
+    logs: Logs,
+    script_hash: String,
+  },
+  DecideValue {
+    value: Decimal,
+    logs: Logs,
+    script_hash: String,
+  },
+  DecideInvalid {
+    logs: Logs,
+    script_hash: String,
+  },
+  DecideSplit {
+    allocations: Vec<Allocation>,
+    logs: Logs,
+    script_hash: String,
+  },
+  DecideRanking {
+    ranking: Vec<u32>,
+    logs: Logs,
+    script_hash: String,
+  },
+  Cancel {
+    reason: String,
+  },
+  CosignCancel {},
   Claim {},
+  Resolve {},
+  SubmitAttestations {
+    attestations: Vec<Attestation>,
+  },
+  SubmitSignedResult {
+    payload: Binary,
+    signature: Binary,
+  },
+  SubmitBeacon {
+    round: u64,
+    signature: Binary,
+    previous_signature: Binary,
+  },
+  AddHook {
+    addr: String,
+  },
+  RemoveHook {
+    addr: String,
+  },
+  UpdateTrial {
+    prompt: Option<String>,
+    choice_texts: Option<Vec<String>>,
+    style: Option<Style>,
+  },
+  ConfigureChoices {
+    choices: Vec<String>,
+  },
+  ConfigureJury {
+    jury: Vec<JurorIntiatiationParams>,
+    jury_pool: Option<JuryPool>,
+  },
+  ConfigureCancelPolicy {
+    cancel_policy: CancelPolicy,
+  },
+  ConfigureVerdict {
+    verdict: Box<Verdict>,
+  },
+  AppendScript {
+    chunk: String,
+  },
+  Publish {},
+  ProposeOwner {
+    new_owner: String,
+    expiry: Option<Expiration>,
+  },
+  AcceptOwnership {},
+  RenounceOwnership {},
+  SetGuardian {
+    guardian: Option<String>,
+  },
+  Pause {
+    operation: PausableOperation,
+    seconds: u64,
+  },
+  Unpause {
+    operation: PausableOperation,
+  },
 }
 
 /// Custom contract query endpoints.
@@ -37,9 +142,205 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
   GetBallot {},
+  GetCancellation {},
+  GetStats {},
+  GetLeaderboard {
+    choice: Option<u32>,
+    limit: Option<u32>,
+  },
+  GetOddsHistory {
+    start_after: Option<u64>,
+    limit: Option<u32>,
+  },
+  GetHistory {
+    start_after: Option<u64>,
+    limit: Option<u32>,
+  },
+  GetOdds {},
+  GetJuryPool {},
+  GetScript {},
+  GetJurorLogs {
+    juror: Addr,
+  },
+  GetResolution {},
+  GetHooks {},
+  GetConfigHash {},
+  GetOwnership {},
+  GetPauseState {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetTrialResponse {
   pub trial: Trial,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetCancellationResponse {
+  pub cancellation: Option<Cancellation>,
+  /// The jurors who have co-signed a cancellation so far.
+  pub cosigners: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetStatsResponse {
+  pub stats: Stats,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaderboardEntry {
+  pub address: Addr,
+  pub choice: u32,
+  pub weight: u32,
+  pub payout: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetLeaderboardResponse {
+  pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetOddsHistoryResponse {
+  pub snapshots: Vec<OddsSnapshot>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetHistoryResponse {
+  pub entries: Vec<HistoryEntry>,
+}
+
+/// The response expected from an oracle contract's query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OracleValueResponse {
+  pub value: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ChoiceOdds {
+  pub choice: u32,
+  pub text: String,
+  pub weight: u32,
+  /// The share of the voters' pot allocated to the choice. Before a verdict,
+  /// this is the share the choice would receive as the sole winner.
+  pub share: Decimal,
+  /// The amount paid out per unit of weight on the choice, divided by the
+  /// price of a unit of weight, or `None` if the choice has no weight.
+  pub multiplier: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetOddsResponse {
+  /// The total amount paid in by voters.
+  pub pot: Uint128,
+  /// The amount to be split among winning voters, net of juror fees.
+  pub voters_pot: Uint128,
+  pub choices: Vec<ChoiceOdds>,
+  /// The shares of the voters' pot allocated to each winning choice, once the
+  /// trial has a verdict. Each winning voter receives the portion of each
+  /// share proportional to their weight on the choice.
+  pub allocations: Option<Vec<Allocation>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetJuryPoolResponse {
+  pub jury_pool: Option<JuryPool>,
+  /// The drand round that selects the panel.
+  pub round: Option<u64>,
+  /// The beacon that selected the panel, once submitted.
+  pub beacon: Option<Beacon>,
+  pub panel: Vec<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetScriptResponse {
+  pub script: Script,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetJurorLogsResponse {
+  pub logs: Option<Logs>,
+}
+
+/// The trial's outcome, for use by other contracts. Versioned independently of
+/// `GetTrialResponse`, so that its shape stays stable as the trial grows.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetResolutionResponse {
+  pub version: u32,
+  pub resolved: bool,
+  pub winner: Option<u32>,
+  pub status: Status,
+  pub resolved_at: Option<Timestamp>,
+}
+
+/// The digest of the trial's configuration as published, if it has been.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetConfigHashResponse {
+  pub config_hash: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetOwnershipResponse {
+  pub owner: Option<Addr>,
+  pub pending_owner: Option<Addr>,
+  pub pending_expiry: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OperationPauseState {
+  pub operation: PausableOperation,
+  pub paused: bool,
+  pub until: Option<Timestamp>,
+  /// How long the operation has been paused for in total, including the rest
+  /// of any pause in effect.
+  pub seconds_used: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPauseStateResponse {
+  pub guardian: Option<Addr>,
+  pub max_pause_seconds: u64,
+  pub operations: Vec<OperationPauseState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetHooksResponse {
+  pub hooks: Vec<Addr>,
+}
+
+/// Sent to each registered hook contract whenever the trial's status changes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerdictHookMsg {
+  pub trial: Addr,
+  pub from: Status,
+  pub to: Status,
+  pub winner: Option<u32>,
+}
+
+impl VerdictHookMsg {
+  /// Serialize the msg, wrapped in the `verdict_hook` variant that hook
+  /// contracts must handle.
+  pub fn into_binary(self) -> StdResult<Binary> {
+    to_binary(&VerdictHookExecuteMsg::VerdictHook(self))
+  }
+
+  pub fn into_cosmos_msg(
+    self,
+    contract_addr: String,
+  ) -> StdResult<CosmosMsg> {
+    Ok(
+      WasmMsg::Execute {
+        contract_addr,
+        msg: self.into_binary()?,
+        funds: vec![],
+      }
+      .into(),
+    )
+  }
+}
+
+/// The execute msg of a hook contract, as far as this contract is concerned.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum VerdictHookExecuteMsg {
+  VerdictHook(VerdictHookMsg),
+}
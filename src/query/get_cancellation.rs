@@ -0,0 +1,16 @@
+use crate::{
+  msg::GetCancellationResponse,
+  state::{CANCELLATION, CANCEL_COSIGNERS},
+};
+use cosmwasm_std::{Deps, Order, StdResult};
+
+pub fn get_cancellation(deps: Deps) -> StdResult<GetCancellationResponse> {
+  let cancellation = CANCELLATION.may_load(deps.storage)?;
+  let cosigners = CANCEL_COSIGNERS
+    .keys(deps.storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<_>>>()?;
+  Ok(GetCancellationResponse {
+    cancellation,
+    cosigners,
+  })
+}
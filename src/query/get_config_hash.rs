@@ -0,0 +1,9 @@
+use crate::{msg::GetConfigHashResponse, state::CONFIG_HASH};
+use cosmwasm_std::{Deps, StdResult};
+
+/// Return the digest of the trial's configuration as published, if it has been.
+pub fn get_config_hash(deps: Deps) -> StdResult<GetConfigHashResponse> {
+  Ok(GetConfigHashResponse {
+    config_hash: CONFIG_HASH.may_load(deps.storage)?,
+  })
+}
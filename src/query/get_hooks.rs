@@ -0,0 +1,9 @@
+use crate::{msg::GetHooksResponse, state::HOOKS};
+use cosmwasm_std::{Deps, StdResult};
+
+/// Return the contracts notified of changes in the trial's status.
+pub fn get_hooks(deps: Deps) -> StdResult<GetHooksResponse> {
+  Ok(GetHooksResponse {
+    hooks: HOOKS.load(deps.storage)?,
+  })
+}
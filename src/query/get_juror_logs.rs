@@ -0,0 +1,21 @@
+use crate::{
+  msg::GetJurorLogsResponse,
+  state::{JURORS, JUROR_LOGS},
+};
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+/// Return the logs uploaded by a juror, including the logs themselves if
+/// stored inline.
+pub fn get_juror_logs(
+  deps: Deps,
+  juror: Addr,
+) -> StdResult<GetJurorLogsResponse> {
+  let mut logs = JURORS
+    .may_load(deps.storage, juror.clone())?
+    .and_then(|juror| juror.logs);
+  if let Some(logs) = logs.as_mut() {
+    logs.inline = JUROR_LOGS.may_load(deps.storage, juror)?;
+  }
+
+  Ok(GetJurorLogsResponse { logs })
+}
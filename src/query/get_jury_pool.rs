@@ -0,0 +1,29 @@
+use crate::{
+  msg::GetJuryPoolResponse,
+  state::{BEACON, JURORS, JURY_POOL, TRIAL},
+};
+use cosmwasm_std::{Deps, Order, StdResult};
+
+/// Return the trial's jury pool, if any, along with the drand round that
+/// selects the panel and, once selected, the beacon and the panel itself.
+pub fn get_jury_pool(deps: Deps) -> StdResult<GetJuryPoolResponse> {
+  let trial = TRIAL.load(deps.storage)?;
+  let jury_pool = JURY_POOL.may_load(deps.storage)?;
+  let beacon = BEACON.may_load(deps.storage)?;
+  let panel = if beacon.is_some() {
+    JURORS
+      .keys(deps.storage, None, None, Order::Ascending)
+      .collect::<StdResult<_>>()?
+  } else {
+    vec![]
+  };
+
+  Ok(GetJuryPoolResponse {
+    round: jury_pool
+      .as_ref()
+      .map(|jury_pool| jury_pool.drand.round_after(trial.verdict.start)),
+    jury_pool,
+    beacon,
+    panel,
+  })
+}
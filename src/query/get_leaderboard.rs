@@ -0,0 +1,123 @@
+use crate::{
+  models::{Allocation, Trial},
+  msg::{GetLeaderboardResponse, LeaderboardEntry},
+  state::{ALLOCATIONS, CHOICE_VOTES_BY_WEIGHT, TRIAL, VOTERS_TOTAL_CLAIM_AMOUNT, VOTES_BY_WEIGHT},
+};
+use cosmwasm_std::{Addr, Deps, Order, StdResult, Uint128};
+use std::cmp::Reverse;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 50;
+
+/// Rank the largest positions by weight, either overall or for the given
+/// choice. Once the trial has a verdict, each entry includes the payout owed
+/// to the position, and if no choice is given, the positions on the winning
+/// choices are ranked by payout, i.e. the biggest winners.
+pub fn get_leaderboard(
+  deps: Deps,
+  choice: Option<u32>,
+  limit: Option<u32>,
+) -> StdResult<GetLeaderboardResponse> {
+  let trial = TRIAL.load(deps.storage)?;
+  let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+  // the amount to split among winning voters, if a verdict has been reached
+  let payouts = if trial.has_verdict() {
+    Some(Payouts {
+      voters_total_amount: VOTERS_TOTAL_CLAIM_AMOUNT.load(deps.storage)?,
+      allocations: ALLOCATIONS.load(deps.storage)?,
+    })
+  } else {
+    None
+  };
+
+  let entries = match (choice, &payouts) {
+    (Some(choice_index), _) => top_choice_positions(deps, choice_index, limit)?
+      .into_iter()
+      .map(|(weight, address)| build_entry(&trial, &payouts, address, choice_index, weight))
+      .collect(),
+    (None, Some(winnings)) => {
+      // the top positions of each winning choice are the only candidates for
+      // the top payouts overall
+      let mut entries: Vec<LeaderboardEntry> = vec![];
+      for allocation in winnings.allocations.iter() {
+        for (weight, address) in top_choice_positions(deps, allocation.choice, limit)? {
+          entries.push(build_entry(
+            &trial,
+            &payouts,
+            address,
+            allocation.choice,
+            weight,
+          ));
+        }
+      }
+      entries.sort_by_key(|entry| Reverse(entry.payout));
+      entries.truncate(limit);
+      entries
+    },
+    (None, None) => VOTES_BY_WEIGHT
+      .keys(deps.storage, None, None, Order::Descending)
+      .take(limit)
+      .map(|result| {
+        result.map(|(weight, choice_index, address)| {
+          build_entry(&trial, &payouts, address, choice_index, weight)
+        })
+      })
+      .collect::<StdResult<_>>()?,
+  };
+
+  Ok(GetLeaderboardResponse { entries })
+}
+
+struct Payouts {
+  voters_total_amount: Uint128,
+  allocations: Vec<Allocation>,
+}
+
+impl Payouts {
+  fn payout(
+    &self,
+    trial: &Trial,
+    choice_index: u32,
+    weight: u32,
+  ) -> Uint128 {
+    self
+      .allocations
+      .iter()
+      .find(|allocation| allocation.choice == choice_index)
+      .map(|allocation| {
+        let choice_weight = trial.choices[choice_index as usize].weight;
+        (self.voters_total_amount * allocation.share).multiply_ratio(weight, choice_weight)
+      })
+      .unwrap_or_default()
+  }
+}
+
+fn top_choice_positions(
+  deps: Deps,
+  choice_index: u32,
+  limit: usize,
+) -> StdResult<Vec<(u32, Addr)>> {
+  CHOICE_VOTES_BY_WEIGHT
+    .sub_prefix(choice_index)
+    .keys(deps.storage, None, None, Order::Descending)
+    .take(limit)
+    .collect()
+}
+
+fn build_entry(
+  trial: &Trial,
+  payouts: &Option<Payouts>,
+  address: Addr,
+  choice_index: u32,
+  weight: u32,
+) -> LeaderboardEntry {
+  LeaderboardEntry {
+    payout: payouts
+      .as_ref()
+      .map(|payouts| payouts.payout(trial, choice_index, weight)),
+    address,
+    choice: choice_index,
+    weight,
+  }
+}
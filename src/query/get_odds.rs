@@ -0,0 +1,75 @@
+use crate::{
+  msg::{ChoiceOdds, GetOddsResponse},
+  state::{ALLOCATIONS, JURORS, TRIAL, VOTERS_TOTAL_CLAIM_AMOUNT},
+};
+use cosmwasm_std::{Decimal, Deps, Order, StdResult, Uint128};
+
+/// Explain how the pot would be paid out to each choice. Before a verdict, each
+/// choice's odds are those of it being the sole winner, or placing first in a
+/// ranked trial, net of juror fees. Once the trial has a verdict, the pot is
+/// split by the verdict's allocations, so a split or ranked verdict pays each
+/// winning choice only its share.
+pub fn get_odds(deps: Deps) -> StdResult<GetOddsResponse> {
+  let trial = TRIAL.load(deps.storage)?;
+  let pot = trial.pot();
+
+  let (voters_pot, allocations) = if trial.has_verdict() {
+    (
+      VOTERS_TOTAL_CLAIM_AMOUNT.load(deps.storage)?,
+      Some(ALLOCATIONS.load(deps.storage)?),
+    )
+  } else {
+    // the jury would be paid its full fees out of the pot
+    let mut jury_pct = 0u128;
+    for juror_result in JURORS.range(deps.storage, None, None, Order::Ascending) {
+      let (_juror_addr, juror) = juror_result?;
+      jury_pct += juror.pct as u128;
+    }
+    (pot - pot.multiply_ratio(jury_pct, 100u128), None)
+  };
+
+  // the share of a choice that takes first place. a draft's payout table
+  // isn't validated yet, so it may be empty, in which case nothing is paid out.
+  let top_share = match &trial.verdict.payout_table {
+    Some(payout_table) => payout_table
+      .first()
+      .map(|pct| Decimal::percent(*pct as u64))
+      .unwrap_or_default(),
+    None => Decimal::one(),
+  };
+
+  let choices = trial
+    .choices
+    .iter()
+    .enumerate()
+    .map(|(i, choice)| {
+      let share = match &allocations {
+        Some(allocations) => allocations
+          .iter()
+          .find(|allocation| allocation.choice as usize == i)
+          .map(|allocation| allocation.share)
+          .unwrap_or_default(),
+        None => top_share,
+      };
+      let staked = trial.price * Uint128::from(choice.weight);
+      ChoiceOdds {
+        choice: i as u32,
+        text: choice.text.clone(),
+        weight: choice.weight,
+        share,
+        multiplier: if staked.is_zero() {
+          None
+        } else {
+          Some(Decimal::from_ratio(voters_pot * share, staked))
+        },
+      }
+    })
+    .collect();
+
+  Ok(GetOddsResponse {
+    pot,
+    voters_pot,
+    choices,
+    allocations,
+  })
+}
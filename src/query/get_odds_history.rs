@@ -0,0 +1,21 @@
+use crate::{msg::GetOddsHistoryResponse, state::ODDS_HISTORY};
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+const DEFAULT_LIMIT: u32 = 30;
+const MAX_LIMIT: u32 = 100;
+
+pub fn get_odds_history(
+  deps: Deps,
+  start_after: Option<u64>,
+  limit: Option<u32>,
+) -> StdResult<GetOddsHistoryResponse> {
+  let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+  let start = start_after.map(Bound::exclusive);
+  let snapshots = ODDS_HISTORY
+    .range(deps.storage, start, None, Order::Ascending)
+    .take(limit)
+    .map(|result| result.map(|(_bucket, snapshot)| snapshot))
+    .collect::<StdResult<_>>()?;
+  Ok(GetOddsHistoryResponse { snapshots })
+}
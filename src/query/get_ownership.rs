@@ -0,0 +1,16 @@
+use crate::{
+  msg::GetOwnershipResponse,
+  state::{PENDING_OWNER, TRIAL},
+};
+use cosmwasm_std::{Deps, StdResult};
+
+/// Return the trial's current owner, along with any proposed owner.
+pub fn get_ownership(deps: Deps) -> StdResult<GetOwnershipResponse> {
+  let trial = TRIAL.load(deps.storage)?;
+  let pending = PENDING_OWNER.may_load(deps.storage)?;
+  Ok(GetOwnershipResponse {
+    owner: trial.owner,
+    pending_owner: pending.as_ref().map(|pending| pending.owner.clone()),
+    pending_expiry: pending.map(|pending| pending.expiry),
+  })
+}
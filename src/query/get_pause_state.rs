@@ -0,0 +1,34 @@
+use crate::{
+  models::PausableOperation,
+  msg::{GetPauseStateResponse, OperationPauseState},
+  state::{GUARDIAN, MAX_PAUSE_SECONDS, PAUSES},
+};
+use cosmwasm_std::{Deps, Env, StdResult};
+
+/// Return the guardian, if any, and whether each pausable operation is paused.
+pub fn get_pause_state(
+  deps: Deps,
+  env: Env,
+) -> StdResult<GetPauseStateResponse> {
+  let operations = PausableOperation::ALL
+    .iter()
+    .map(|operation| -> StdResult<_> {
+      let pause = PAUSES.may_load(deps.storage, operation.to_string())?;
+      let until = pause
+        .as_ref()
+        .map(|pause| pause.until)
+        .filter(|until| env.block.time < *until);
+      Ok(OperationPauseState {
+        operation: *operation,
+        paused: until.is_some(),
+        until,
+        seconds_used: pause.map_or(0, |pause| pause.seconds_used),
+      })
+    })
+    .collect::<StdResult<Vec<_>>>()?;
+  Ok(GetPauseStateResponse {
+    guardian: GUARDIAN.may_load(deps.storage)?,
+    max_pause_seconds: MAX_PAUSE_SECONDS,
+    operations,
+  })
+}
@@ -0,0 +1,17 @@
+use crate::{
+  msg::GetResolutionResponse,
+  state::{RESOLUTION, RESOLUTION_VERSION},
+};
+use cosmwasm_std::{Deps, StdResult};
+
+/// Return whether and how the trial was resolved, without loading the trial.
+pub fn get_resolution(deps: Deps) -> StdResult<GetResolutionResponse> {
+  let resolution = RESOLUTION.load(deps.storage)?;
+  Ok(GetResolutionResponse {
+    version: RESOLUTION_VERSION,
+    resolved: resolution.resolved,
+    winner: resolution.winner,
+    status: resolution.status,
+    resolved_at: resolution.resolved_at,
+  })
+}
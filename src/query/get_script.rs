@@ -0,0 +1,17 @@
+use crate::{
+  models::Script,
+  msg::GetScriptResponse,
+  state::{SCRIPT_BODY, TRIAL},
+};
+use cosmwasm_std::{Deps, StdResult};
+
+/// Return the trial's decision script, including its body if stored inline.
+pub fn get_script(deps: Deps) -> StdResult<GetScriptResponse> {
+  let trial = TRIAL.load(deps.storage)?;
+  Ok(GetScriptResponse {
+    script: Script {
+      body: SCRIPT_BODY.may_load(deps.storage)?,
+      ..trial.verdict.script
+    },
+  })
+}
@@ -0,0 +1,7 @@
+use crate::{msg::GetStatsResponse, state::STATS};
+use cosmwasm_std::{Deps, StdResult};
+
+pub fn get_stats(deps: Deps) -> StdResult<GetStatsResponse> {
+  let stats = STATS.load(deps.storage)?;
+  Ok(GetStatsResponse { stats })
+}
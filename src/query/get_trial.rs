@@ -3,5 +3,5 @@ use cosmwasm_std::{Deps, StdResult};
 
 pub fn get_trial(deps: Deps) -> StdResult<GetTrialResponse> {
   let trial = TRIAL.load(deps.storage)?;
-  Ok(GetTrialResponse { trial: trial })
+  Ok(GetTrialResponse { trial })
 }
@@ -1,3 +1,31 @@
+mod get_cancellation;
+mod get_config_hash;
+mod get_history;
+mod get_hooks;
+mod get_juror_logs;
+mod get_jury_pool;
+mod get_leaderboard;
+mod get_odds;
+mod get_odds_history;
+mod get_ownership;
+mod get_pause_state;
+mod get_resolution;
+mod get_script;
+mod get_stats;
 mod get_trial;
 
+pub use get_cancellation::get_cancellation;
+pub use get_config_hash::get_config_hash;
+pub use get_history::get_history;
+pub use get_hooks::get_hooks;
+pub use get_juror_logs::get_juror_logs;
+pub use get_jury_pool::get_jury_pool;
+pub use get_leaderboard::get_leaderboard;
+pub use get_odds::get_odds;
+pub use get_odds_history::get_odds_history;
+pub use get_ownership::get_ownership;
+pub use get_pause_state::get_pause_state;
+pub use get_resolution::get_resolution;
+pub use get_script::get_script;
+pub use get_stats::get_stats;
 pub use get_trial::get_trial;
@@ -1,27 +1,95 @@
 use crate::error::ContractError;
-use crate::models::{Choice, Juror, Status, Trial, Vote};
-use crate::msg::InstantiateMsg;
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Uint128};
+use crate::events;
+use crate::models::{
+  Allocation, Beacon, Cancellation, Choice, HistoryAction, HistoryEntry, Juror, OddsSnapshot,
+  Oracle, OutputFormat, PausableOperation, Pause, PendingOwner, Resolution, Runtime, Scalar,
+  Script, Stats, Status, Trial, Verdict, Vote,
+};
+use crate::msg::{InstantiateMsg, JurorIntiatiationParams, JuryPool, VerdictHookMsg};
+use crate::util::{hash_config, hash_script};
+use cosmwasm_std::{
+  Addr, Api, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult, Storage, SubMsg,
+  Uint128,
+};
 use cw_storage_plus::{Item, Map};
 
 pub const TRIAL: Item<Trial> = Item::new("trial");
 pub const VOTES: Map<(u32, Addr), Vote> = Map::new("votes");
+pub const VOTER_WEIGHTS: Map<Addr, u32> = Map::new("voter_weights");
+/// Secondary indices over VOTES, sorted by weight, used to rank positions
+/// overall and per choice.
+pub const VOTES_BY_WEIGHT: Map<(u32, u32, Addr), Empty> = Map::new("votes_by_weight");
+pub const CHOICE_VOTES_BY_WEIGHT: Map<(u32, u32, Addr), Empty> = Map::new("choice_votes_by_weight");
 pub const JURORS: Map<Addr, Juror> = Map::new("decisions");
 pub const HAS_CLAIMED: Map<Addr, bool> = Map::new("has_claimed");
 pub const VOTERS_TOTAL_CLAIM_AMOUNT: Item<Uint128> = Item::new("voters_total_claim_amount");
-pub const CANCEL_REASON: Item<String> = Item::new("cancel_reason");
+pub const ALLOCATIONS: Item<Vec<Allocation>> = Item::new("allocations");
+pub const CANCELLATION: Item<Cancellation> = Item::new("cancellation");
+pub const STATS: Item<Stats> = Item::new("stats");
+pub const ODDS_INTERVAL_MINUTES: Item<u32> = Item::new("odds_interval_minutes");
+pub const ODDS_HISTORY: Map<u64, OddsSnapshot> = Map::new("odds_history");
+pub const HISTORY: Map<u64, HistoryEntry> = Map::new("history");
+pub const HISTORY_SEQ: Item<u64> = Item::new("history_seq");
+pub const JURY_POOL: Item<JuryPool> = Item::new("jury_pool");
+pub const BEACON: Item<Beacon> = Item::new("beacon");
+pub const SCRIPT_BODY: Item<String> = Item::new("script_body");
+pub const JUROR_LOGS: Map<Addr, String> = Map::new("juror_logs");
+pub const RESOLUTION: Item<Resolution> = Item::new("resolution");
+pub const HOOKS: Item<Vec<Addr>> = Item::new("hooks");
+pub const CONFIG_HASH: Item<String> = Item::new("config_hash");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+pub const PAUSES: Map<String, Pause> = Map::new("pauses");
+pub const CANCEL_COSIGNERS: Map<Addr, bool> = Map::new("cancel_cosigners");
 
-/// Initialize contract state data.
+pub const DEFAULT_ODDS_INTERVAL_MINUTES: u32 = 10;
+/// The percentage of their usual fee paid to jurors who rule a trial invalid.
+pub const DEFAULT_INVALID_FEE_PCT: u8 = 50;
+
+pub const DEFAULT_MAX_INLINE_LOGS: u32 = 4096;
+
+pub const MAX_RUNTIME_ARGS: usize = 32;
+pub const MAX_RUNTIME_ENV_VARS: usize = 32;
+pub const MAX_RUNTIME_STRING_LEN: usize = 256;
+
+/// The version of the `get_resolution` response, bumped whenever its shape
+/// changes.
+pub const RESOLUTION_VERSION: u32 = 1;
+
+/// The most hook contracts that can be registered, bounding the number of msgs
+/// sent on each transition.
+pub const MAX_HOOKS: usize = 10;
+/// The gas each hook may spend handling a transition, so that a hook can't
+/// exhaust the gas of the transaction that triggered it.
+pub const HOOK_GAS_LIMIT: u64 = 500_000;
+
+/// The longest an operation can be paused for in total, over any number of
+/// pauses, so that pausing can't be used to hold funds hostage.
+pub const MAX_PAUSE_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// The longest reason, in characters, that can be given for a cancellation.
+pub const MAX_CANCEL_REASON_LEN: usize = 280;
+
+/// Initialize contract state data. The trial starts out as a draft, which the
+/// owner can go on configuring until it's published, so it's only validated
+/// once published.
 pub fn initialize(
   deps: DepsMut,
   _env: &Env,
   info: &MessageInfo,
   msg: &InstantiateMsg,
 ) -> Result<(), ContractError> {
+  let odds_interval_minutes = msg
+    .odds_interval_minutes
+    .unwrap_or(DEFAULT_ODDS_INTERVAL_MINUTES);
+  if odds_interval_minutes == 0 {
+    return Err(ContractError::ValidationError {});
+  }
+
   // initialize trial
-  let trial = Trial {
-    status: Status::Active,
-    owner: info.sender.clone(),
+  let mut trial = Trial {
+    status: Status::Draft,
+    owner: Some(info.sender.clone()),
     prompt: msg.prompt.clone(),
     token: msg.token.clone(),
     style: msg.style.clone(),
@@ -29,37 +97,507 @@ pub fn initialize(
     winner: None,
     weight: 0,
     price: msg.price,
-    choices: msg
-      .choices
-      .iter()
-      .map(|text| Choice {
-        text: text.clone(),
-        weight: 0,
-        tally: 0,
-      })
-      .collect(),
+    choices: vec![],
+    cancel_policy: msg.cancel_policy.clone().unwrap_or_default(),
+    decide_paused_seconds: 0,
   };
 
+  set_choices(&mut trial, &msg.choices);
+  set_verdict(deps.storage, &mut trial, &msg.verdict)?;
+
   // initialize decision records
-  for params in msg.jury.iter() {
-    JURORS.save(
-      deps.storage,
-      params.address.clone(),
-      // TODO: validate juror
-      &Juror {
-        address: params.address.clone(),
-        name: params.name.clone(),
-        url: params.url.clone(),
-        pct: params.pct,
-        choice: None,
-        logs: None,
-      },
-    )?;
-  }
+  save_jury(deps.storage, &msg.jury, msg.jury_pool.as_ref())?;
 
-  // TODO: validate trial
   TRIAL.save(deps.storage, &trial)?;
   VOTERS_TOTAL_CLAIM_AMOUNT.save(deps.storage, &Uint128::zero())?;
+  STATS.save(deps.storage, &Stats::default())?;
+  ODDS_INTERVAL_MINUTES.save(deps.storage, &odds_interval_minutes)?;
+  HISTORY_SEQ.save(deps.storage, &0)?;
+  HOOKS.save(deps.storage, &vec![])?;
+  RESOLUTION.save(
+    deps.storage,
+    &Resolution {
+      resolved: false,
+      winner: None,
+      status: trial.status,
+      resolved_at: None,
+    },
+  )?;
+
+  Ok(())
+}
+
+/// Replace the trial's choices, which must not have been voted on yet. The
+/// trial must still be saved by the caller.
+pub fn set_choices(
+  trial: &mut Trial,
+  choices: &[String],
+) {
+  trial.choices = choices
+    .iter()
+    .map(|text| Choice {
+      text: text.clone(),
+      weight: 0,
+      tally: 0,
+      voters: 0,
+    })
+    .collect();
+}
+
+/// Replace the trial's verdict. An inline script is stored apart from the
+/// trial, so as not to bloat it, replacing any script stored before. The trial
+/// must still be saved by the caller.
+pub fn set_verdict(
+  storage: &mut dyn Storage,
+  trial: &mut Trial,
+  verdict: &Verdict,
+) -> Result<(), ContractError> {
+  match &verdict.script.body {
+    Some(body) => SCRIPT_BODY.save(storage, body)?,
+    None => SCRIPT_BODY.remove(storage),
+  }
+  trial.verdict = Verdict {
+    script: Script {
+      body: None,
+      ..verdict.script.clone()
+    },
+    ..verdict.clone()
+  };
+  Ok(())
+}
+
+/// Replace the trial's jury, and the pool it's drawn from, if any.
+pub fn save_jury(
+  storage: &mut dyn Storage,
+  jury: &[JurorIntiatiationParams],
+  jury_pool: Option<&JuryPool>,
+) -> Result<(), ContractError> {
+  let addrs: Vec<Addr> = JURORS
+    .keys(storage, None, None, Order::Ascending)
+    .collect::<StdResult<_>>()?;
+  for addr in addrs {
+    JURORS.remove(storage, addr);
+  }
+  for params in jury.iter() {
+    save_juror(storage, params)?;
+  }
+  match jury_pool {
+    Some(jury_pool) => JURY_POOL.save(storage, jury_pool)?,
+    None => JURY_POOL.remove(storage),
+  }
+  Ok(())
+}
+
+/// Hash the trial's configuration as it now stands, i.e. the trial, its jury
+/// and its jury pool, and keep the digest for the `get_config_hash` query.
+pub fn save_config_hash(
+  storage: &mut dyn Storage,
+  trial: &Trial,
+) -> StdResult<String> {
+  let jury: Vec<Juror> = JURORS
+    .range(storage, None, None, Order::Ascending)
+    .map(|result| result.map(|(_, juror)| juror))
+    .collect::<StdResult<_>>()?;
+  let config_hash = hash_config(trial, &jury, JURY_POOL.may_load(storage)?.as_ref())?;
+  CONFIG_HASH.save(storage, &config_hash)?;
+  Ok(config_hash)
+}
 
+/// Validate the trial's configuration in full, as it's published. Voting must
+/// not have closed yet, and jurors' fees can't exceed the pot.
+pub fn validate_trial(
+  deps: Deps,
+  env: &Env,
+  trial: &Trial,
+) -> Result<(), ContractError> {
+  let verdict = &trial.verdict;
+  let n_choices = trial.choices.len();
+  let jurors = JURORS
+    .range(deps.storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<_>>>()?;
+  let n_jurors = jurors.len();
+  let jury_pool = JURY_POOL.may_load(deps.storage)?;
+  if n_choices == 0 || trial.is_voting_closed(env.block.time) {
+    return Err(ContractError::ValidationError {});
+  }
+  if !(1..=100).contains(&trial.cancel_policy.juror_quorum_pct) {
+    return Err(ContractError::ValidationError {});
+  }
+  for (juror_addr, _) in jurors.iter() {
+    deps.api.addr_validate(juror_addr.as_str())?;
+  }
+  if jurors
+    .iter()
+    .map(|(_, juror)| juror.pct as u32)
+    .sum::<u32>()
+    > 100
+  {
+    return Err(ContractError::ValidationError {});
+  }
+
+  validate_script(&Script {
+    body: SCRIPT_BODY.may_load(deps.storage)?,
+    ..verdict.script.clone()
+  })?;
+  if let Some(runtime) = &verdict.runtime {
+    validate_runtime(runtime, verdict)?;
+  }
+  if verdict.invalid_fee_pct.unwrap_or(DEFAULT_INVALID_FEE_PCT) > 100 {
+    return Err(ContractError::ValidationError {});
+  }
+  if let Some(scalar) = &verdict.scalar {
+    validate_scalar(scalar, n_choices)?;
+  }
+  if let Some(payout_table) = &verdict.payout_table {
+    // ranked trials are decided by a jury ranking the choices
+    if verdict.scalar.is_some() || verdict.oracle.is_some() {
+      return Err(ContractError::ValidationError {});
+    }
+    validate_payout_table(payout_table, n_choices)?;
+  }
+  if let Some(provider) = &verdict.provider {
+    // provider-resolved trials have neither a jury nor an oracle
+    if provider.pubkey.len() != 32 || n_jurors > 0 || verdict.oracle.is_some() {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  if let Some(jury_pool) = &jury_pool {
+    // the jury is drawn from the pool rather than named upfront
+    if n_jurors > 0 || verdict.oracle.is_some() || verdict.provider.is_some() {
+      return Err(ContractError::ValidationError {});
+    }
+    validate_jury_pool(deps.api, jury_pool)?;
+  }
+  if let Some(oracle) = &verdict.oracle {
+    validate_oracle(
+      deps.api,
+      oracle,
+      n_choices,
+      n_jurors,
+      verdict.scalar.is_some(),
+    )?;
+  }
+  Ok(())
+}
+
+/// A scalar trial must have one well-formed range per choice, in order, where
+/// each range begins where the previous one ends.
+fn validate_scalar(
+  scalar: &Scalar,
+  n_choices: usize,
+) -> Result<(), ContractError> {
+  if scalar.ranges.len() != n_choices || n_choices == 0 {
+    return Err(ContractError::ValidationError {});
+  }
+  for (i, range) in scalar.ranges.iter().enumerate() {
+    if range.min >= range.max {
+      return Err(ContractError::ValidationError {});
+    }
+    if i > 0 && scalar.ranges[i - 1].max != range.min {
+      return Err(ContractError::ValidationError {});
+    }
+  }
   Ok(())
 }
+
+/// A script hash must be a lowercase hex-encoded sha256 digest, matching the
+/// script itself if stored inline.
+fn validate_script(script: &Script) -> Result<(), ContractError> {
+  if !is_sha256_hex(&script.hash) {
+    return Err(ContractError::ValidationError {});
+  }
+  if let Some(body) = &script.body {
+    if hash_script(body) != script.hash {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  Ok(())
+}
+
+/// A runtime must name a version made of alphanumerics and `.+-_`, and a
+/// lockfile hash in the same form as script hashes. Arguments and environment
+/// variables are bounded in number and length, and each variable must have a
+/// unique, shell-safe name. The script's output must fit how the trial is
+/// decided: a value for scalar trials, a ranking for ranked trials, and
+/// otherwise a choice or allocations.
+fn validate_runtime(
+  runtime: &Runtime,
+  verdict: &Verdict,
+) -> Result<(), ContractError> {
+  if runtime.version.is_empty()
+    || runtime.version.len() > MAX_RUNTIME_STRING_LEN
+    || !runtime
+      .version
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || ".+-_".contains(c))
+  {
+    return Err(ContractError::ValidationError {});
+  }
+  if let Some(lockfile_hash) = &runtime.lockfile_hash {
+    if !is_sha256_hex(lockfile_hash) {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  if runtime.args.len() > MAX_RUNTIME_ARGS
+    || runtime
+      .args
+      .iter()
+      .any(|arg| arg.len() > MAX_RUNTIME_STRING_LEN)
+  {
+    return Err(ContractError::ValidationError {});
+  }
+  if runtime.env.len() > MAX_RUNTIME_ENV_VARS {
+    return Err(ContractError::ValidationError {});
+  }
+  for (i, var) in runtime.env.iter().enumerate() {
+    let is_valid_name = var
+      .name
+      .chars()
+      .next()
+      .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+      && var
+        .name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_valid_name
+      || var.name.len() > MAX_RUNTIME_STRING_LEN
+      || var.value.len() > MAX_RUNTIME_STRING_LEN
+      || runtime.env[..i].iter().any(|other| other.name == var.name)
+    {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  let is_valid_output = match runtime.output {
+    OutputFormat::Value => verdict.scalar.is_some(),
+    OutputFormat::Ranking => verdict.payout_table.is_some(),
+    OutputFormat::Choice | OutputFormat::Allocations => {
+      verdict.scalar.is_none() && verdict.payout_table.is_none()
+    },
+  };
+  if !is_valid_output {
+    return Err(ContractError::ValidationError {});
+  }
+  Ok(())
+}
+
+/// Whether a string is a lowercase hex-encoded sha256 digest.
+pub fn is_sha256_hex(hash: &str) -> bool {
+  hash.len() == 64
+    && hash
+      .chars()
+      .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+/// A juror's public key, if any, must be a compressed or uncompressed
+/// secp256k1 public key.
+fn validate_juror_pubkey(params: &JurorIntiatiationParams) -> Result<(), ContractError> {
+  if let Some(pubkey) = &params.pubkey {
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  Ok(())
+}
+
+/// Register a member of the jury, who hasn't yet decided.
+pub fn save_juror(
+  storage: &mut dyn Storage,
+  params: &JurorIntiatiationParams,
+) -> Result<(), ContractError> {
+  validate_juror_pubkey(params)?;
+  JURORS.save(
+    storage,
+    params.address.clone(),
+    &Juror {
+      address: params.address.clone(),
+      name: params.name.clone(),
+      url: params.url.clone(),
+      pct: params.pct,
+      choice: None,
+      ruling: None,
+      logs: None,
+      pubkey: params.pubkey.clone(),
+    },
+  )?;
+  Ok(())
+}
+
+/// A jury pool must have at least as many distinct candidates as seats on the
+/// panel, each with a valid address and public key, and fees that can't exceed
+/// the pot whichever panel is selected. The drand network must have a 48-byte
+/// public key and a non-zero period.
+fn validate_jury_pool(
+  api: &dyn Api,
+  jury_pool: &JuryPool,
+) -> Result<(), ContractError> {
+  let panel_size = jury_pool.panel_size as usize;
+  if panel_size == 0 || panel_size > jury_pool.candidates.len() {
+    return Err(ContractError::ValidationError {});
+  }
+  for (i, candidate) in jury_pool.candidates.iter().enumerate() {
+    api.addr_validate(candidate.address.as_str())?;
+    validate_juror_pubkey(candidate)?;
+    if jury_pool.candidates[..i]
+      .iter()
+      .any(|other| other.address == candidate.address)
+    {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  let mut pcts: Vec<u32> = jury_pool
+    .candidates
+    .iter()
+    .map(|candidate| candidate.pct as u32)
+    .collect();
+  pcts.sort_unstable_by(|a, b| b.cmp(a));
+  if pcts.iter().take(panel_size).sum::<u32>() > 100 {
+    return Err(ContractError::ValidationError {});
+  }
+  if jury_pool.drand.pubkey.len() != 48 || jury_pool.drand.period_seconds == 0 {
+    return Err(ContractError::ValidationError {});
+  }
+  Ok(())
+}
+
+/// A payout table must have a positive, non-increasing percent for each place,
+/// with no more places than choices, summing to 100.
+fn validate_payout_table(
+  payout_table: &[u8],
+  n_choices: usize,
+) -> Result<(), ContractError> {
+  if payout_table.is_empty() || payout_table.len() > n_choices {
+    return Err(ContractError::ValidationError {});
+  }
+  for (i, pct) in payout_table.iter().enumerate() {
+    if *pct == 0 || (i > 0 && payout_table[i - 1] < *pct) {
+      return Err(ContractError::ValidationError {});
+    }
+  }
+  if payout_table.iter().map(|pct| *pct as u32).sum::<u32>() != 100 {
+    return Err(ContractError::ValidationError {});
+  }
+  Ok(())
+}
+
+/// An oracle must be a valid address with at least one rule, each mapping a
+/// well-formed range to an existing choice, unless the trial is scalar, in
+/// which case the value is mapped by the scalar ranges. Oracle-resolved trials
+/// have no jury.
+fn validate_oracle(
+  api: &dyn Api,
+  oracle: &Oracle,
+  n_choices: usize,
+  n_jurors: usize,
+  is_scalar: bool,
+) -> Result<(), ContractError> {
+  api.addr_validate(oracle.address.as_str())?;
+  if (oracle.rules.is_empty() && !is_scalar) || n_jurors > 0 {
+    return Err(ContractError::ValidationError {});
+  }
+  for rule in oracle.rules.iter() {
+    if rule.choice as usize >= n_choices {
+      return Err(ContractError::InvalidChoice {});
+    }
+    if let (Some(min), Some(max)) = (rule.min, rule.max) {
+      if min >= max {
+        return Err(ContractError::ValidationError {});
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Abort if the operation is paused.
+pub fn ensure_not_paused(
+  storage: &dyn Storage,
+  env: &Env,
+  operation: PausableOperation,
+) -> Result<(), ContractError> {
+  if let Some(pause) = PAUSES.may_load(storage, operation.to_string())? {
+    if env.block.time < pause.until {
+      return Err(ContractError::Paused {
+        operation,
+        until: pause.until,
+      });
+    }
+  }
+  Ok(())
+}
+
+/// Append an entry to the audit log, returning its sequence number.
+pub fn append_history(
+  storage: &mut dyn Storage,
+  env: &Env,
+  actor: &Addr,
+  action: HistoryAction,
+) -> Result<u64, ContractError> {
+  let seq = HISTORY_SEQ.load(storage)?;
+  HISTORY.save(
+    storage,
+    seq,
+    &HistoryEntry {
+      seq,
+      actor: actor.clone(),
+      height: env.block.height,
+      time: env.block.time,
+      action,
+    },
+  )?;
+  HISTORY_SEQ.save(storage, &(seq + 1))?;
+  Ok(seq)
+}
+
+/// Change the trial's status, recording the transition in the audit log and
+/// updating the trial's resolution. Returns a partial response, to be merged
+/// into the caller's, with the transition's event and the msgs notifying each
+/// registered hook of it. Hook msgs are sent with `reply_on_error`, so that a
+/// failing hook can't block the transition. The trial itself must still be
+/// saved by the caller, and its winner, if any, must be set beforehand.
+pub fn transition(
+  storage: &mut dyn Storage,
+  env: &Env,
+  actor: &Addr,
+  trial: &mut Trial,
+  status: Status,
+) -> Result<Response, ContractError> {
+  let from = trial.status.clone();
+  trial.status = status.clone();
+  append_history(
+    storage,
+    env,
+    actor,
+    HistoryAction::Transition {
+      from: from.clone(),
+      to: status.clone(),
+    },
+  )?;
+  RESOLUTION.save(
+    storage,
+    &Resolution {
+      resolved: trial.is_resolved(),
+      winner: trial.winner,
+      status: trial.status.clone(),
+      resolved_at: if trial.is_resolved() {
+        Some(env.block.time)
+      } else {
+        None
+      },
+    },
+  )?;
+
+  // each hook's reply id is its index, by which a failure is reported
+  let mut response = Response::new().add_event(events::transition(&from, &status, trial.winner));
+  for (i, hook) in HOOKS.load(storage)?.into_iter().enumerate() {
+    let msg = VerdictHookMsg {
+      trial: env.contract.address.clone(),
+      from: from.clone(),
+      to: status.clone(),
+      winner: trial.winner,
+    };
+    response = response.add_submessage(
+      SubMsg::reply_on_error(msg.into_cosmos_msg(hook.to_string())?, i as u64)
+        .with_gas_limit(HOOK_GAS_LIMIT),
+    );
+  }
+  Ok(response)
+}
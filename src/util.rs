@@ -1,7 +1,13 @@
-use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, DepsMut, SubMsg, Uint128, WasmMsg};
+use cosmwasm_std::{
+  to_binary, to_vec, Addr, BankMsg, Coin, CosmosMsg, DepsMut, Response, StdResult, SubMsg, Uint128,
+  WasmMsg,
+};
 use cw20::{Cw20ExecuteMsg, Cw20QueryMsg};
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
+use crate::models::{AttestationPayload, Juror, Ruling, Trial};
+use crate::msg::JuryPool;
 
 pub fn build_cw20_transfer_msg(
   from_address: &Addr,
@@ -57,7 +63,7 @@ pub fn validate_cw20_funds(
 
 // Check for the exact payment amount required in the tx's funds.
 pub fn validate_native_funds(
-  funds: &Vec<Coin>,
+  funds: &[Coin],
   payment_amount: Uint128,
   denom: &String,
 ) -> Result<(), ContractError> {
@@ -72,3 +78,55 @@ pub fn validate_native_funds(
   }
   Ok(())
 }
+
+/// Compute the digest a juror signs to attest to a ruling, i.e. the sha256
+/// digest of the JSON-encoded `AttestationPayload`.
+pub fn attestation_digest(
+  contract: &Addr,
+  ruling: &Ruling,
+  logs_hash: &str,
+  script_hash: &str,
+) -> StdResult<Vec<u8>> {
+  let payload = AttestationPayload {
+    contract: contract.clone(),
+    ruling: ruling.clone(),
+    logs_hash: logs_hash.to_owned(),
+    script_hash: script_hash.to_owned(),
+  };
+  Ok(Sha256::digest(to_vec(&payload)?).to_vec())
+}
+
+/// The lowercase hex-encoded sha256 digest of a script.
+pub fn hash_script(body: &str) -> String {
+  format!("{:x}", Sha256::digest(body.as_bytes()))
+}
+
+/// The lowercase hex-encoded sha256 digest of a juror's logs.
+pub fn hash_logs(logs: &str) -> String {
+  format!("{:x}", Sha256::digest(logs.as_bytes()))
+}
+
+/// The lowercase hex-encoded sha256 digest of the trial's configuration as
+/// published, i.e. of the JSON array of the trial, its jury and its jury pool.
+pub fn hash_config(
+  trial: &Trial,
+  jury: &[Juror],
+  jury_pool: Option<&JuryPool>,
+) -> StdResult<String> {
+  Ok(format!(
+    "{:x}",
+    Sha256::digest(to_vec(&(trial, jury, jury_pool))?)
+  ))
+}
+
+/// Merge the msgs, attributes and events of a partial response, such as one
+/// returned by a state transition, into another response.
+pub fn merge_response(
+  response: Response,
+  part: Response,
+) -> Response {
+  response
+    .add_submessages(part.messages)
+    .add_attributes(part.attributes)
+    .add_events(part.events)
+}
@@ -0,0 +1,192 @@
+mod common;
+
+use common::{logs, Suite, SCRIPT_HASH};
+use cosmwasm_std::{to_vec, Addr, Binary, Event};
+use cw_contract_template::{
+  models::{Attestation, AttestationPayload, Ruling, Status},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::AppResponse;
+use k256::ecdsa::{signature::DigestSigner, Signature, SigningKey};
+use sha2::{Digest, Sha256};
+
+/// Each juror's signing key, derived from their name.
+fn signing_key(juror: &str) -> SigningKey {
+  SigningKey::from_bytes(&Sha256::digest(juror.as_bytes())).unwrap()
+}
+
+/// The default trial, with each juror registered under their compressed
+/// public key.
+fn attested_suite() -> Suite {
+  Suite::with(|_, msg| {
+    for juror in msg.jury.iter_mut() {
+      let pubkey = signing_key(juror.address.as_str())
+        .verifying_key()
+        .to_bytes();
+      juror.pubkey = Some(Binary(pubkey.to_vec()));
+    }
+  })
+}
+
+/// An attestation to the ruling, with logs of "ok", signed by `signer` for the
+/// given contract.
+fn attestation(
+  juror: &str,
+  signer: &str,
+  contract: &Addr,
+  ruling: Ruling,
+) -> Attestation {
+  let logs = logs("ok");
+  let payload = AttestationPayload {
+    contract: contract.clone(),
+    ruling: ruling.clone(),
+    logs_hash: logs.hash.clone(),
+    script_hash: SCRIPT_HASH.to_owned(),
+  };
+  let signature: Signature =
+    signing_key(signer).sign_digest(Sha256::new().chain_update(to_vec(&payload).unwrap()));
+  Attestation {
+    juror: Addr::unchecked(juror),
+    ruling,
+    logs,
+    script_hash: SCRIPT_HASH.to_owned(),
+    signature: Binary(signature.as_ref().to_vec()),
+  }
+}
+
+fn submit(
+  suite: &mut Suite,
+  attestations: Vec<Attestation>,
+) -> anyhow::Result<AppResponse> {
+  suite.execute(
+    "relayer",
+    &ExecuteMsg::SubmitAttestations { attestations },
+    &[],
+  )
+}
+
+#[test]
+fn relayed_batch_decides_the_trial() {
+  let mut suite = attested_suite();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+
+  let trial = suite.trial.clone();
+  let resp = submit(
+    &mut suite,
+    vec![
+      attestation("dave", "dave", &trial, Ruling::Choice(1)),
+      attestation("erin", "erin", &trial, Ruling::Choice(1)),
+    ],
+  )
+  .unwrap();
+  assert!(resp.has_event(&Event::new("wasm").add_attribute("attestations", "2")));
+  let trial = suite.trial();
+  assert_eq!(trial.status, Status::HasVerdict);
+  assert_eq!(trial.winner, Some(1));
+}
+
+#[test]
+fn invalid_attestations_are_rejected() {
+  let mut suite = attested_suite();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  let trial = suite.trial.clone();
+
+  // signed by someone other than the juror
+  let err = submit(
+    &mut suite,
+    vec![attestation("dave", "erin", &trial, Ruling::Choice(1))],
+  )
+  .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "InvalidSignature");
+
+  // signed for another trial
+  let err = submit(
+    &mut suite,
+    vec![attestation(
+      "dave",
+      "dave",
+      &Addr::unchecked("other"),
+      Ruling::Choice(1),
+    )],
+  )
+  .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "InvalidSignature");
+
+  // one invalid attestation fails the whole batch
+  assert!(submit(
+    &mut suite,
+    vec![
+      attestation("dave", "dave", &trial, Ruling::Choice(1)),
+      attestation("erin", "dave", &trial, Ruling::Choice(1)),
+    ],
+  )
+  .is_err());
+  assert_eq!(suite.trial().status, Status::Active);
+}
+
+#[test]
+fn jurors_without_a_pubkey_cannot_attest() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+
+  let trial = suite.trial.clone();
+  let err = submit(
+    &mut suite,
+    vec![attestation("dave", "dave", &trial, Ruling::Choice(1))],
+  )
+  .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "NoPubkey");
+}
+
+#[test]
+fn attestations_cannot_be_replayed_over_a_newer_decision() {
+  let mut suite = attested_suite();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+
+  let trial = suite.trial.clone();
+  let old = attestation("dave", "dave", &trial, Ruling::Choice(0));
+  submit(&mut suite, vec![old.clone()]).unwrap();
+  suite.decide("dave", Ruling::Choice(1)).unwrap();
+
+  let err = submit(&mut suite, vec![old]).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "StaleAttestation");
+
+  // attesting to the same ruling again is harmless
+  submit(
+    &mut suite,
+    vec![attestation("dave", "dave", &trial, Ruling::Choice(1))],
+  )
+  .unwrap();
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+  assert_eq!(suite.trial().winner, Some(1));
+}
+
+#[test]
+fn a_batch_attests_to_each_juror_once() {
+  let mut suite = attested_suite();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+
+  // the second attestation would otherwise override the first
+  let trial = suite.trial.clone();
+  let err = submit(
+    &mut suite,
+    vec![
+      attestation("dave", "dave", &trial, Ruling::Choice(0)),
+      attestation("dave", "dave", &trial, Ruling::Choice(1)),
+      attestation("erin", "erin", &trial, Ruling::Choice(1)),
+    ],
+  )
+  .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "DuplicateAttestation");
+
+  // even if both attest to the same ruling
+  let dave = attestation("dave", "dave", &trial, Ruling::Choice(1));
+  let err = submit(&mut suite, vec![dave.clone(), dave]).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "DuplicateAttestation");
+  assert_eq!(suite.trial().status, Status::Active);
+}
@@ -0,0 +1,211 @@
+mod common;
+
+use common::{use_oracle, Suite, BALANCE, DENOM};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_contract_template::{
+  models::{CancelPolicy, Ruling, Status},
+  msg::{ExecuteMsg, GetCancellationResponse, QueryMsg},
+};
+use cw_multi_test::Executor;
+
+#[test]
+fn owner_can_still_cancel_before_deliberations() {
+  let mut suite = Suite::with_oracle("5");
+  suite.vote("alice", 0, 2);
+
+  let owner = suite.owner.clone();
+  suite
+    .app
+    .execute_contract(
+      owner,
+      suite.trial.clone(),
+      &ExecuteMsg::Cancel {
+        reason: "feed halted".to_owned(),
+      },
+      &[],
+    )
+    .unwrap();
+  assert!(suite.resolve().is_err());
+
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), 10_000);
+}
+
+#[test]
+fn cancel_after_voting_closes_requires_juror_quorum_and_bond() {
+  // both jurors must co-sign
+  let mut suite = Suite::draft(|_, msg| {
+    msg.cancel_policy = Some(CancelPolicy {
+      bond: Uint128::from(500u128),
+      juror_quorum_pct: 51,
+    })
+  });
+  let owner = suite.owner.clone();
+  suite.publish().unwrap();
+  suite.vote("alice", 0, 2);
+  suite.close_voting();
+
+  let cancel = |reason: &str| ExecuteMsg::Cancel {
+    reason: reason.to_owned(),
+  };
+  let cosign = |suite: &mut Suite, juror: &str| {
+    suite.app.execute_contract(
+      Addr::unchecked(juror),
+      suite.trial.clone(),
+      &ExecuteMsg::CosignCancel {},
+      &[],
+    )
+  };
+
+  // both jurors must co-sign once deliberations have begun
+  let err = suite
+    .app
+    .execute_contract(
+      owner.clone(),
+      suite.trial.clone(),
+      &cancel("feed halted"),
+      &[],
+    )
+    .unwrap_err();
+  assert!(err.root_cause().to_string().contains("0 of 2"));
+  assert!(cosign(&mut suite, "alice").is_err());
+  cosign(&mut suite, "dave").unwrap();
+  assert!(cosign(&mut suite, "dave").is_err());
+  cosign(&mut suite, "erin").unwrap();
+
+  // the reason is required and bounded
+  for reason in [" ".to_owned(), "x".repeat(281)] {
+    assert!(suite
+      .app
+      .execute_contract(
+        owner.clone(),
+        suite.trial.clone(),
+        &cancel(&reason),
+        &coins(500, DENOM),
+      )
+      .is_err());
+  }
+  // and the bond must be posted
+  assert!(suite
+    .app
+    .execute_contract(
+      owner.clone(),
+      suite.trial.clone(),
+      &cancel("feed halted"),
+      &[]
+    )
+    .is_err());
+  suite
+    .app
+    .execute_contract(
+      owner,
+      suite.trial.clone(),
+      &cancel("feed halted"),
+      &coins(500, DENOM),
+    )
+    .unwrap();
+
+  // the voter is refunded along with the bond
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), 10_000 + 500);
+}
+
+#[test]
+fn trial_without_a_jury_hangs_once_deliberations_expire() {
+  // the oracle's value matches no rule, so the trial can't be resolved
+  let mut suite = Suite::with(|app, msg| {
+    use_oracle(app, msg, "5");
+    msg.verdict.oracle.as_mut().unwrap().rules.remove(0);
+  });
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  assert!(suite.resolve().is_err());
+
+  // there are no jurors to co-sign, so the owner can't cancel
+  let err = suite.cancel("feed out of range").unwrap_err();
+  assert!(err.root_cause().to_string().contains("0 of 1"));
+  assert!(suite.claim("alice").is_err());
+
+  suite.expire_deliberations();
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.trial().status, Status::HungJury);
+  assert_eq!(suite.balance("alice"), BALANCE);
+}
+
+#[test]
+fn owner_can_cancel_without_cosigners_once_deliberations_expire() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  assert!(suite.cancel("erin went silent").is_err());
+
+  suite.expire_deliberations();
+  suite.cancel("erin went silent").unwrap();
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), BALANCE);
+}
+
+#[test]
+fn anyone_who_voted_is_refunded_once_a_silent_jury_expires() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.vote("bob", 1, 1);
+  // without an owner, nobody can cancel the trial
+  suite
+    .execute("owner", &ExecuteMsg::RenounceOwnership {}, &[])
+    .unwrap();
+  suite.close_voting();
+  assert!(suite.claim("alice").is_err());
+
+  suite.expire_deliberations();
+  assert!(suite.cancel("jury went silent").is_err());
+  // carol didn't vote, so her claim reverts and the trial is unchanged;
+  // alice's claim is the one that hangs the jury
+  let status = suite.trial().status;
+  assert!(suite.claim("carol").is_err());
+  assert_eq!(suite.trial().status, status);
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.trial().status, Status::HungJury);
+  assert_eq!(suite.balance("alice"), BALANCE);
+  assert!(suite.claim("alice").is_err());
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE);
+
+  let stats = suite.stats();
+  assert_eq!(stats.liabilities, Uint128::zero());
+  assert_eq!(stats.claimants_remaining, 0);
+}
+
+#[test]
+fn cancellation_is_recorded_with_its_cosigners() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  let resp: GetCancellationResponse = suite.query(&QueryMsg::GetCancellation {});
+  assert_eq!(resp.cancellation, None);
+  assert!(resp.cosigners.is_empty());
+
+  suite.close_voting();
+  suite
+    .execute("dave", &ExecuteMsg::CosignCancel {}, &[])
+    .unwrap();
+  let resp: GetCancellationResponse = suite.query(&QueryMsg::GetCancellation {});
+  assert_eq!(resp.cancellation, None);
+  assert_eq!(resp.cosigners, vec![Addr::unchecked("dave")]);
+
+  suite
+    .execute("erin", &ExecuteMsg::CosignCancel {}, &[])
+    .unwrap();
+  suite.cancel("feed halted").unwrap();
+  let resp: GetCancellationResponse = suite.query(&QueryMsg::GetCancellation {});
+  let cancellation = resp.cancellation.unwrap();
+  let jurors = vec![Addr::unchecked("dave"), Addr::unchecked("erin")];
+  assert_eq!(cancellation.canceled_by, suite.owner);
+  assert_eq!(cancellation.canceled_at, suite.app.block_info().time);
+  assert_eq!(cancellation.height, suite.app.block_info().height);
+  assert_eq!(cancellation.reason, "feed halted");
+  assert_eq!(cancellation.refundable, Uint128::from(200u128));
+  assert_eq!(cancellation.bond, Uint128::zero());
+  assert_eq!(cancellation.cosigners, jurors);
+  assert_eq!(resp.cosigners, jurors);
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::{Suite, BALANCE};
+use cw_contract_template::models::{Ruling, Status};
+
+#[test]
+fn refunds_cannot_be_claimed_twice() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.vote("bob", 1, 1);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+  assert_eq!(suite.trial().status, Status::HungJury);
+
+  suite.claim("alice").unwrap();
+  let err = suite.claim("alice").unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "HasClaimed");
+  assert_eq!(suite.balance("alice"), BALANCE);
+  // alice's second claim didn't eat into bob's refund
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE);
+}
+
+#[test]
+fn rewards_are_proportional_to_weight_within_a_choice() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 1);
+  suite.vote("bob", 0, 3);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  suite.decide("erin", Ruling::Choice(0)).unwrap();
+
+  // each voter's weight is a fraction of the choice's, which must not be
+  // rounded down to nothing: they share 360 of the pot of 400 by weight
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), BALANCE - 100 + 90);
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE - 300 + 270);
+}
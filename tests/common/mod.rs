@@ -0,0 +1,493 @@
+//! A trial deployed to a mock chain, along with mock oracle and hook contracts,
+//! shared by the contract's integration tests.
+#![allow(dead_code)]
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+  coins, to_binary, Addr, Binary, Coin, Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+  StdError, StdResult, Timestamp, Uint128,
+};
+use cw_contract_template::{
+  contract::{execute, instantiate, query, reply},
+  models::{
+    Background, Font, Logs, Oracle, OracleRule, ProgrammingLanguage, Ruling, Script, Stats, Status,
+    Style, Token, Trial, Verdict,
+  },
+  msg::{
+    ExecuteMsg, GetStatsResponse, GetTrialResponse, InstantiateMsg, JurorIntiatiationParams,
+    QueryMsg, VerdictHookMsg,
+  },
+};
+use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+pub const DENOM: &str = "ujuno";
+pub const PRICE: u128 = 100;
+/// The balance each voter, and the owner, starts out with.
+pub const BALANCE: u128 = 10_000;
+pub const VOTERS: [&str; 3] = ["alice", "bob", "carol"];
+/// The jurors of the default trial, each paid `JUROR_PCT` percent of the pot.
+pub const JURORS: [&str; 2] = ["dave", "erin"];
+pub const JUROR_PCT: u8 = 5;
+/// The digest of the default trial's script, which is empty.
+pub const SCRIPT_HASH: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+/// Voting closes an hour after instantiation, and deliberations last an hour.
+pub const VOTING_SECONDS: u64 = 3600;
+pub const DELIBERATION_MINUTES: u32 = 60;
+
+/// A mock oracle contract that reports whatever value it was instantiated with.
+pub mod mock_oracle {
+  use super::*;
+
+  pub const VALUE: Item<Decimal> = Item::new("value");
+
+  #[cw_serde]
+  pub struct InstantiateMsg {
+    pub value: Decimal,
+  }
+
+  #[cw_serde]
+  pub enum QueryMsg {
+    Price { symbol: String },
+  }
+
+  pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+  ) -> StdResult<Response> {
+    VALUE.save(deps.storage, &msg.value)?;
+    Ok(Response::new())
+  }
+
+  pub fn execute(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+  ) -> StdResult<Response> {
+    Ok(Response::new())
+  }
+
+  pub fn query(
+    deps: Deps,
+    _env: Env,
+    msg: QueryMsg,
+  ) -> StdResult<Binary> {
+    match msg {
+      QueryMsg::Price { .. } => to_binary(&cw_contract_template::msg::OracleValueResponse {
+        value: VALUE.load(deps.storage)?,
+      }),
+    }
+  }
+}
+
+/// A mock hook contract that records the last status it was notified of, or
+/// fails every notification if instantiated to.
+pub mod mock_hook {
+  use super::*;
+
+  pub const FAIL: Item<bool> = Item::new("fail");
+  pub const LAST: Item<Status> = Item::new("last");
+
+  #[cw_serde]
+  pub struct InstantiateMsg {
+    pub fail: bool,
+  }
+
+  #[cw_serde]
+  pub enum ExecuteMsg {
+    VerdictHook(VerdictHookMsg),
+  }
+
+  #[cw_serde]
+  pub enum QueryMsg {
+    Last {},
+  }
+
+  pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+  ) -> StdResult<Response> {
+    FAIL.save(deps.storage, &msg.fail)?;
+    Ok(Response::new())
+  }
+
+  pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ExecuteMsg,
+  ) -> StdResult<Response> {
+    if FAIL.load(deps.storage)? {
+      return Err(StdError::generic_err("hook failed"));
+    }
+    match msg {
+      ExecuteMsg::VerdictHook(msg) => LAST.save(deps.storage, &msg.to)?,
+    }
+    Ok(Response::new())
+  }
+
+  pub fn query(
+    deps: Deps,
+    _env: Env,
+    msg: QueryMsg,
+  ) -> StdResult<Binary> {
+    match msg {
+      QueryMsg::Last {} => to_binary(&LAST.may_load(deps.storage)?),
+    }
+  }
+}
+
+/// The default trial, asking where a price will close, with choices "low",
+/// "mid" and "high". Voting closes at `start`, after which the jury has an
+/// hour to decide.
+pub fn trial_msg(start: Timestamp) -> InstantiateMsg {
+  InstantiateMsg {
+    prompt: "Where will the price close?".to_owned(),
+    choices: vec!["low".to_owned(), "mid".to_owned(), "high".to_owned()],
+    verdict: Verdict {
+      script: Script {
+        hash: SCRIPT_HASH.to_owned(),
+        uri: None,
+        body: Some(String::new()),
+      },
+      language: ProgrammingLanguage::Bash,
+      runtime: None,
+      start,
+      minutes: DELIBERATION_MINUTES,
+      oracle: None,
+      scalar: None,
+      invalid_fee_pct: None,
+      payout_table: None,
+      provider: None,
+      max_inline_logs: None,
+    },
+    token: Token::Native {
+      denom: DENOM.to_owned(),
+    },
+    price: Uint128::from(PRICE),
+    style: Style {
+      background: Background::Value("#000".to_owned()),
+      font: Font {
+        family: "mono".to_owned(),
+        color: "#fff".to_owned(),
+      },
+    },
+    jury: JURORS.iter().map(|juror| juror_params(juror)).collect(),
+    odds_interval_minutes: None,
+    jury_pool: None,
+    cancel_policy: None,
+  }
+}
+
+/// A juror paid `JUROR_PCT` percent of the pot, without a public key.
+pub fn juror_params(address: &str) -> JurorIntiatiationParams {
+  JurorIntiatiationParams {
+    address: Addr::unchecked(address),
+    name: address.to_owned(),
+    url: None,
+    pct: JUROR_PCT,
+    pubkey: None,
+  }
+}
+
+/// Resolve the trial through a mock oracle reporting `value` rather than a
+/// jury, where "low" is below 10, "mid" from 10 to 20 and "high" from 20.
+pub fn use_oracle(
+  app: &mut App,
+  msg: &mut InstantiateMsg,
+  value: &str,
+) {
+  let code_id = app.store_code(Box::new(ContractWrapper::new(
+    mock_oracle::execute,
+    mock_oracle::instantiate,
+    mock_oracle::query,
+  )));
+  let oracle = app
+    .instantiate_contract(
+      code_id,
+      Addr::unchecked("owner"),
+      &mock_oracle::InstantiateMsg {
+        value: Decimal::from_str(value).unwrap(),
+      },
+      &[],
+      "oracle",
+      None,
+    )
+    .unwrap();
+  let bound = |value: &str| Some(Decimal::from_str(value).unwrap());
+  msg.jury = vec![];
+  msg.verdict.oracle = Some(Oracle {
+    address: oracle,
+    query: to_binary(&mock_oracle::QueryMsg::Price {
+      symbol: "JUNO".to_owned(),
+    })
+    .unwrap(),
+    rules: vec![
+      OracleRule {
+        choice: 0,
+        min: None,
+        max: bound("10"),
+      },
+      OracleRule {
+        choice: 1,
+        min: bound("10"),
+        max: bound("20"),
+      },
+      OracleRule {
+        choice: 2,
+        min: bound("20"),
+        max: None,
+      },
+    ],
+  });
+}
+
+/// Inline logs of a script run.
+pub fn logs(text: &str) -> Logs {
+  Logs {
+    hash: format!("{:x}", Sha256::digest(text.as_bytes())),
+    len: text.len() as u64,
+    uri: None,
+    inline: Some(text.to_owned()),
+  }
+}
+
+/// The msg with which a juror submits a ruling, along with the given logs.
+pub fn decide_msg(
+  ruling: Ruling,
+  logs: Logs,
+) -> ExecuteMsg {
+  let script_hash = SCRIPT_HASH.to_owned();
+  match ruling {
+    Ruling::Choice(choice) => ExecuteMsg::Decide {
+      choice,
+      logs,
+      script_hash,
+    },
+    Ruling::Value(value) => ExecuteMsg::DecideValue {
+      value,
+      logs,
+      script_hash,
+    },
+    Ruling::Split(allocations) => ExecuteMsg::DecideSplit {
+      allocations,
+      logs,
+      script_hash,
+    },
+    Ruling::Ranking(ranking) => ExecuteMsg::DecideRanking {
+      ranking,
+      logs,
+      script_hash,
+    },
+    Ruling::Invalid => ExecuteMsg::DecideInvalid { logs, script_hash },
+  }
+}
+
+pub struct Suite {
+  pub app: App,
+  pub owner: Addr,
+  pub trial: Addr,
+}
+
+impl Suite {
+  /// The default trial, published and open for voting.
+  pub fn new() -> Self {
+    Self::with(|_, _| {})
+  }
+
+  /// A trial resolved by a mock oracle reporting `value`, published and open
+  /// for voting.
+  pub fn with_oracle(value: &str) -> Self {
+    Self::with(|app, msg| use_oracle(app, msg, value))
+  }
+
+  /// The default trial as changed by `configure`, published and open for
+  /// voting.
+  pub fn with(configure: impl FnOnce(&mut App, &mut InstantiateMsg)) -> Self {
+    let mut suite = Self::draft(configure);
+    suite.publish().unwrap();
+    suite
+  }
+
+  /// The default trial as changed by `configure`, without publishing it.
+  pub fn draft(configure: impl FnOnce(&mut App, &mut InstantiateMsg)) -> Self {
+    let owner = Addr::unchecked("owner");
+    let mut app = App::new(|router, _api, storage| {
+      for account in VOTERS.iter().chain(["owner"].iter()) {
+        router
+          .bank
+          .init_balance(storage, &Addr::unchecked(*account), coins(BALANCE, DENOM))
+          .unwrap();
+      }
+    });
+
+    let mut msg = trial_msg(app.block_info().time.plus_seconds(VOTING_SECONDS));
+    configure(&mut app, &mut msg);
+    let code_id = app.store_code(Box::new(
+      ContractWrapper::new(execute, instantiate, query).with_reply(reply),
+    ));
+    let trial = app
+      .instantiate_contract(code_id, owner.clone(), &msg, &[], "trial", None)
+      .unwrap();
+
+    Suite { app, owner, trial }
+  }
+
+  pub fn execute(
+    &mut self,
+    sender: &str,
+    msg: &ExecuteMsg,
+    funds: &[Coin],
+  ) -> anyhow::Result<AppResponse> {
+    self
+      .app
+      .execute_contract(Addr::unchecked(sender), self.trial.clone(), msg, funds)
+  }
+
+  pub fn query<T: DeserializeOwned>(
+    &self,
+    msg: &QueryMsg,
+  ) -> T {
+    self
+      .app
+      .wrap()
+      .query_wasm_smart(self.trial.clone(), msg)
+      .unwrap()
+  }
+
+  pub fn publish(&mut self) -> anyhow::Result<AppResponse> {
+    self.execute("owner", &ExecuteMsg::Publish {}, &[])
+  }
+
+  pub fn vote(
+    &mut self,
+    voter: &str,
+    choice: u32,
+    weight: u32,
+  ) -> AppResponse {
+    self
+      .execute(
+        voter,
+        &ExecuteMsg::Vote { choice, weight },
+        &coins(PRICE * weight as u128, DENOM),
+      )
+      .unwrap()
+  }
+
+  /// Submit a juror's ruling, with the logs every juror agrees on.
+  pub fn decide(
+    &mut self,
+    juror: &str,
+    ruling: Ruling,
+  ) -> anyhow::Result<AppResponse> {
+    self.execute(juror, &decide_msg(ruling, logs("ok")), &[])
+  }
+
+  pub fn resolve(&mut self) -> anyhow::Result<AppResponse> {
+    self.execute("anyone", &ExecuteMsg::Resolve {}, &[])
+  }
+
+  pub fn claim(
+    &mut self,
+    voter: &str,
+  ) -> anyhow::Result<AppResponse> {
+    self.execute(voter, &ExecuteMsg::Claim {}, &[])
+  }
+
+  pub fn cancel(
+    &mut self,
+    reason: &str,
+  ) -> anyhow::Result<AppResponse> {
+    self.execute(
+      "owner",
+      &ExecuteMsg::Cancel {
+        reason: reason.to_owned(),
+      },
+      &[],
+    )
+  }
+
+  /// Instantiate a mock hook contract and register it with the trial.
+  pub fn add_hook(
+    &mut self,
+    fail: bool,
+  ) -> Addr {
+    let code_id = self.app.store_code(Box::new(ContractWrapper::new(
+      mock_hook::execute,
+      mock_hook::instantiate,
+      mock_hook::query,
+    )));
+    let hook = self
+      .app
+      .instantiate_contract(
+        code_id,
+        self.owner.clone(),
+        &mock_hook::InstantiateMsg { fail },
+        &[],
+        "hook",
+        None,
+      )
+      .unwrap();
+    self
+      .execute(
+        "owner",
+        &ExecuteMsg::AddHook {
+          addr: hook.to_string(),
+        },
+        &[],
+      )
+      .unwrap();
+    hook
+  }
+
+  pub fn advance(
+    &mut self,
+    seconds: u64,
+  ) {
+    self.app.update_block(|block| {
+      block.time = block.time.plus_seconds(seconds);
+      block.height += seconds / 6;
+    });
+  }
+
+  /// Advance to the moment voting closes, from instantiation.
+  pub fn close_voting(&mut self) {
+    self.advance(VOTING_SECONDS);
+  }
+
+  /// Advance past the end of deliberations, from the moment voting closed.
+  pub fn expire_deliberations(&mut self) {
+    self.advance(60 * DELIBERATION_MINUTES as u64 + 1);
+  }
+
+  pub fn trial(&self) -> Trial {
+    self
+      .query::<GetTrialResponse>(&QueryMsg::GetBallot {})
+      .trial
+  }
+
+  pub fn stats(&self) -> Stats {
+    self.query::<GetStatsResponse>(&QueryMsg::GetStats {}).stats
+  }
+
+  pub fn balance(
+    &self,
+    address: &str,
+  ) -> u128 {
+    self
+      .app
+      .wrap()
+      .query_balance(address, DENOM)
+      .unwrap()
+      .amount
+      .u128()
+  }
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::{juror_params, use_oracle, Suite, DENOM, PRICE};
+use cosmwasm_std::{coins, Addr};
+use cw_contract_template::{
+  models::Status,
+  msg::{ExecuteMsg, GetConfigHashResponse, QueryMsg},
+};
+use cw_multi_test::Executor;
+
+#[test]
+fn draft_is_configured_then_published() {
+  let mut suite = Suite::draft(|app, msg| use_oracle(app, msg, "12.5"));
+  let vote = suite.app.execute_contract(
+    Addr::unchecked("alice"),
+    suite.trial.clone(),
+    &ExecuteMsg::Vote {
+      choice: 0,
+      weight: 1,
+    },
+    &coins(PRICE, DENOM),
+  );
+  assert!(vote.is_err());
+
+  // an oracle rule refers to a choice that no longer exists
+  let owner = suite.owner.clone();
+  suite
+    .app
+    .execute_contract(
+      owner,
+      suite.trial.clone(),
+      &ExecuteMsg::ConfigureChoices {
+        choices: vec!["low".to_owned(), "high".to_owned()],
+      },
+      &[],
+    )
+    .unwrap();
+  assert!(suite.publish().is_err());
+  assert_eq!(suite.trial().status, Status::Draft);
+
+  let owner = suite.owner.clone();
+  suite
+    .app
+    .execute_contract(
+      owner,
+      suite.trial.clone(),
+      &ExecuteMsg::ConfigureChoices {
+        choices: vec!["low".to_owned(), "mid".to_owned(), "high".to_owned()],
+      },
+      &[],
+    )
+    .unwrap();
+  suite.publish().unwrap();
+  assert_eq!(suite.trial().status, Status::Active);
+  assert!(suite.publish().is_err());
+
+  let resp: GetConfigHashResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetConfigHash {})
+    .unwrap();
+  assert_eq!(resp.config_hash.unwrap().len(), 64);
+  suite.vote("alice", 0, 1);
+}
+
+#[test]
+fn jury_is_validated_on_publish() {
+  let mut suite = Suite::draft(|_, _| {});
+  let configure = |suite: &mut Suite, jury| {
+    suite
+      .execute(
+        "owner",
+        &ExecuteMsg::ConfigureJury {
+          jury,
+          jury_pool: None,
+        },
+        &[],
+      )
+      .unwrap();
+  };
+
+  // jurors' fees would exceed the pot
+  let mut dave = juror_params("dave");
+  dave.pct = 60;
+  let mut erin = juror_params("erin");
+  erin.pct = 41;
+  configure(&mut suite, vec![dave.clone(), erin.clone()]);
+  assert!(suite.publish().is_err());
+
+  // a juror's address isn't valid
+  erin.pct = 40;
+  configure(&mut suite, vec![dave.clone(), juror_params("Erin")]);
+  assert!(suite.publish().is_err());
+
+  configure(&mut suite, vec![dave, erin]);
+  suite.publish().unwrap();
+  assert_eq!(suite.trial().status, Status::Active);
+}
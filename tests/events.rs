@@ -0,0 +1,55 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Event;
+use cw_contract_template::models::Ruling;
+
+#[test]
+fn actions_and_transitions_emit_typed_events() {
+  // a single juror decides as soon as voting closes
+  let mut suite = Suite::with(|_, msg| msg.jury.truncate(1));
+  let resp = suite.vote("alice", 1, 2);
+  let vote = resp
+    .events
+    .iter()
+    .find(|event| event.ty == "wasm-vote")
+    .unwrap();
+  assert!(vote
+    .attributes
+    .iter()
+    .any(|attr| attr.key == "payment" && attr.value == "200"));
+
+  suite.close_voting();
+  let resp = suite.decide("dave", Ruling::Choice(1)).unwrap();
+  let transitions: Vec<(String, String)> = resp
+    .events
+    .iter()
+    .filter(|event| event.ty == "wasm-transition")
+    .map(|event| {
+      let value = |key: &str| {
+        event
+          .attributes
+          .iter()
+          .find(|attr| attr.key == key)
+          .unwrap()
+          .value
+          .clone()
+      };
+      (value("from"), value("to"))
+    })
+    .collect();
+  assert_eq!(
+    transitions,
+    vec![
+      ("active".to_owned(), "deliberating".to_owned()),
+      ("deliberating".to_owned(), "has_verdict".to_owned()),
+    ]
+  );
+  assert!(resp.has_event(&Event::new("wasm-decide").add_attribute("juror", "dave")));
+  assert!(resp.has_event(
+    &Event::new("wasm-juror_fee")
+      .add_attribute("juror", "dave")
+      .add_attribute("amount", "10")
+  ));
+  assert!(resp.has_event(&Event::new("wasm-verdict").add_attribute("winner", "1")));
+}
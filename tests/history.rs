@@ -0,0 +1,82 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Uint128;
+use cw_contract_template::{
+  models::{HistoryAction, HistoryEntry, Ruling, Status},
+  msg::{GetHistoryResponse, QueryMsg},
+};
+
+fn history(
+  suite: &Suite,
+  start_after: Option<u64>,
+  limit: Option<u32>,
+) -> Vec<HistoryEntry> {
+  suite
+    .query::<GetHistoryResponse>(&QueryMsg::GetHistory { start_after, limit })
+    .entries
+}
+
+#[test]
+fn history_is_numbered_in_order() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(1)).unwrap();
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+  suite.claim("alice").unwrap();
+
+  let entries = history(&suite, None, None);
+  let seqs: Vec<u64> = entries.iter().map(|entry| entry.seq).collect();
+  assert_eq!(seqs, (0..8).collect::<Vec<u64>>());
+  let actions: Vec<(&str, &HistoryAction)> = entries
+    .iter()
+    .map(|entry| (entry.actor.as_str(), &entry.action))
+    .collect();
+  let transition = |from, to| HistoryAction::Transition { from, to };
+  let decide = HistoryAction::Decide {
+    ruling: Ruling::Choice(1),
+  };
+  assert_eq!(
+    actions[0],
+    ("owner", &transition(Status::Draft, Status::Active))
+  );
+  assert!(matches!(
+    actions[1],
+    ("owner", HistoryAction::Publish { .. })
+  ));
+  assert_eq!(
+    actions[2..],
+    [
+      (
+        "alice",
+        &HistoryAction::Vote {
+          choice: 1,
+          weight: 2
+        }
+      ),
+      ("dave", &transition(Status::Active, Status::Deliberating)),
+      ("dave", &decide),
+      ("erin", &decide),
+      (
+        "erin",
+        &transition(Status::Deliberating, Status::HasVerdict)
+      ),
+      (
+        "alice",
+        &HistoryAction::Claim {
+          amount: Uint128::from(180u128),
+          refund: false,
+        }
+      ),
+    ]
+  );
+  assert_eq!(entries[7].time, suite.app.block_info().time);
+
+  // pages follow on from the last sequence number seen
+  let page = history(&suite, None, Some(3));
+  assert_eq!(page, entries[..3]);
+  let page = history(&suite, Some(page[2].seq), Some(3));
+  assert_eq!(page, entries[3..6]);
+  assert_eq!(history(&suite, Some(7), None), vec![]);
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::{mock_hook, trial_msg, Suite};
+use cosmwasm_std::{
+  testing::{mock_dependencies, mock_env, mock_info},
+  Addr, ReplyOn,
+};
+use cw_contract_template::{
+  contract,
+  models::Status,
+  msg::{ExecuteMsg, GetResolutionResponse, QueryMsg},
+  state::HOOK_GAS_LIMIT,
+};
+use cw_multi_test::Executor;
+
+#[test]
+fn failing_hook_does_not_block_resolution() {
+  let mut suite = Suite::with_oracle("12.5");
+  suite.add_hook(true);
+  let hook = suite.add_hook(false);
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  suite.resolve().unwrap();
+
+  let resolution: GetResolutionResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetResolution {})
+    .unwrap();
+  assert!(resolution.resolved);
+  assert_eq!(resolution.winner, Some(1));
+  assert_eq!(resolution.status, Status::HasVerdict);
+
+  let last: Option<Status> = suite
+    .app
+    .wrap()
+    .query_wasm_smart(hook, &mock_hook::QueryMsg::Last {})
+    .unwrap();
+  assert_eq!(last, Some(Status::HasVerdict));
+}
+
+#[test]
+fn only_owner_can_add_hooks() {
+  let mut suite = Suite::with_oracle("12.5");
+  let err = suite.app.execute_contract(
+    Addr::unchecked("alice"),
+    suite.trial.clone(),
+    &ExecuteMsg::AddHook {
+      addr: "alice".to_owned(),
+    },
+    &[],
+  );
+  assert!(err.is_err());
+}
+
+// the multi-test app doesn't meter gas, so a hook that burns through its gas
+// can't be simulated there. Instead, check that each hook msg is capped.
+#[test]
+fn hooks_are_sent_with_a_gas_limit() {
+  let mut deps = mock_dependencies();
+  let env = mock_env();
+  let owner = mock_info("owner", &[]);
+  let start = env.block.time.plus_seconds(3600);
+  contract::instantiate(deps.as_mut(), env.clone(), owner.clone(), trial_msg(start)).unwrap();
+  for msg in [
+    ExecuteMsg::Publish {},
+    ExecuteMsg::AddHook {
+      addr: "burner".to_owned(),
+    },
+  ] {
+    contract::execute(deps.as_mut(), env.clone(), owner.clone(), msg).unwrap();
+  }
+
+  let resp = contract::execute(
+    deps.as_mut(),
+    env,
+    owner,
+    ExecuteMsg::Cancel {
+      reason: "feed halted".to_owned(),
+    },
+  )
+  .unwrap();
+  assert_eq!(resp.messages.len(), 1);
+  assert_eq!(resp.messages[0].gas_limit, Some(HOOK_GAS_LIMIT));
+  assert_eq!(resp.messages[0].reply_on, ReplyOn::Error);
+}
@@ -0,0 +1,156 @@
+use cosmwasm_std::{from_slice, to_vec};
+use cw_contract_template::{
+  models::{EnvVar, OutputFormat, Runtime},
+  msg::{ExecuteMsg, GetTrialResponse},
+};
+use sha2::{Digest, Sha256};
+use std::{
+  env, fs,
+  path::PathBuf,
+  process::{Command, Output},
+};
+
+/// Run the juror binary against the example trial, with the given Bash script
+/// standing in for the trial's script.
+fn run_juror(
+  name: &str,
+  script: &str,
+  runtime: Option<Runtime>,
+  extra_args: &[&str],
+) -> Output {
+  let mut resp: GetTrialResponse = from_slice(&fs::read("bin/juror/trial.json").unwrap()).unwrap();
+  resp.trial.verdict.script.hash = format!("{:x}", Sha256::digest(script.as_bytes()));
+  resp.trial.verdict.runtime = runtime;
+
+  let dir = env::temp_dir().join(format!("verdict-juror-test-{}", name));
+  fs::create_dir_all(&dir).unwrap();
+  let trial_path = dir.join("trial.json");
+  let script_path: PathBuf = dir.join("script.sh");
+  fs::write(&trial_path, to_vec(&resp).unwrap()).unwrap();
+  fs::write(&script_path, script).unwrap();
+
+  let output = Command::new(env!("CARGO_BIN_EXE_juror"))
+    .arg("--trial")
+    .arg(&trial_path)
+    .arg("--script")
+    .arg(&script_path)
+    .args(extra_args)
+    .output()
+    .unwrap();
+  fs::remove_dir_all(&dir).unwrap();
+  output
+}
+
+#[test]
+fn example_script_decides_choice_by_text() {
+  let output = Command::new(env!("CARGO_BIN_EXE_juror"))
+    .args(["--trial", "bin/juror/trial.json"])
+    .args(["--script", "bin/juror/example.sh"])
+    .output()
+    .unwrap();
+  assert!(output.status.success());
+
+  let msg: ExecuteMsg = from_slice(&output.stdout).unwrap();
+  match msg {
+    ExecuteMsg::Decide {
+      choice,
+      logs,
+      script_hash,
+    } => {
+      assert_eq!(choice, 0);
+      assert!(logs.inline.unwrap().contains("home 2, away 1"));
+      assert_eq!(
+        script_hash,
+        "d68192f88d89969292ad468eceabe02df3460d83ed651ff6c733072e541a0904"
+      );
+    },
+    _ => panic!("expected a decide msg"),
+  }
+}
+
+#[test]
+fn runtime_pins_args_env_and_output() {
+  let runtime = Runtime {
+    version: "5.1".to_owned(),
+    lockfile_hash: None,
+    args: vec!["0.5".to_owned()],
+    env: vec![EnvVar {
+      name: "SECOND".to_owned(),
+      value: "1".to_owned(),
+    }],
+    output: OutputFormat::Allocations,
+  };
+  let script = r#"echo "[{\"choice\":0,\"share\":\"$1\"},{\"choice\":$SECOND,\"share\":\"0.5\"}]"
+"#;
+  let output = run_juror("runtime", script, Some(runtime), &[]);
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  let msg: ExecuteMsg = from_slice(&output.stdout).unwrap();
+  match msg {
+    ExecuteMsg::DecideSplit { allocations, .. } => {
+      assert_eq!(allocations.len(), 2);
+      assert_eq!(allocations[1].choice, 1);
+    },
+    _ => panic!("expected a decide_split msg"),
+  }
+}
+
+#[test]
+fn rejects_script_not_matching_hash() {
+  let output = Command::new(env!("CARGO_BIN_EXE_juror"))
+    .args(["--trial", "bin/juror/trial.json"])
+    .args(["--script", "bin/juror/trial.json"])
+    .output()
+    .unwrap();
+  assert!(!output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).contains("does not match"));
+}
+
+#[test]
+fn kills_script_after_timeout() {
+  let output = run_juror("timeout", "sleep 5\necho 0\n", None, &["--timeout", "1"]);
+  assert!(!output.status.success());
+  assert!(String::from_utf8_lossy(&output.stderr).contains("timed out"));
+}
+
+#[test]
+fn script_resources_are_capped() {
+  let script = "ulimit -n\nulimit -c\nulimit -f\necho 0\n";
+  let output = run_juror("rlimits", script, None, &[]);
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  let msg: ExecuteMsg = from_slice(&output.stdout).unwrap();
+  match msg {
+    ExecuteMsg::Decide { logs, .. } => {
+      // bash reports file sizes in blocks of 1024 bytes
+      assert_eq!(logs.inline.unwrap(), "256\n0\n65536\n0\n");
+    },
+    _ => panic!("expected a decide msg"),
+  }
+}
+
+#[test]
+fn invalid_output_rules_trial_invalid() {
+  let output = run_juror("invalid", "echo invalid\n", None, &[]);
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+
+  let msg: ExecuteMsg = from_slice(&output.stdout).unwrap();
+  match msg {
+    ExecuteMsg::DecideInvalid { logs, .. } => {
+      assert_eq!(logs.inline.unwrap(), "invalid\n");
+    },
+    _ => panic!("expected a decide_invalid msg"),
+  }
+}
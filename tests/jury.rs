@@ -0,0 +1,98 @@
+mod common;
+
+use common::{Suite, BALANCE};
+use cosmwasm_std::Decimal;
+use cw_contract_template::models::{Allocation, Ruling, Status};
+
+#[test]
+fn first_decision_after_voting_closes_begins_deliberations() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 1);
+  let err = suite.decide("dave", Ruling::Choice(0)).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "NotDeciding");
+
+  suite.close_voting();
+  assert_eq!(suite.trial().status, Status::Active);
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  assert_eq!(suite.trial().status, Status::Deliberating);
+  suite.decide("erin", Ruling::Choice(0)).unwrap();
+  assert_eq!(suite.trial().status, Status::HasVerdict);
+}
+
+#[test]
+fn invalid_ruling_refunds_voters_after_reduced_juror_fees() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.vote("bob", 1, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Invalid).unwrap();
+  suite.decide("erin", Ruling::Invalid).unwrap();
+  assert_eq!(suite.trial().status, Status::Invalid);
+
+  // each juror is paid half of their 5% fee on the pot of 400
+  assert_eq!(suite.balance("dave"), 10);
+  assert_eq!(suite.balance("erin"), 10);
+  // and voters share the remaining 380 by weight
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), BALANCE - 200 + 190);
+  assert!(suite.claim("alice").is_err());
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE - 200 + 190);
+  assert!(suite.claim("carol").is_err());
+}
+
+#[test]
+fn split_claims_sum_each_winning_choice() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.vote("alice", 1, 2);
+  suite.vote("bob", 1, 2);
+  suite.vote("carol", 2, 1);
+  suite.close_voting();
+
+  // a tie between the first two choices
+  let split = Ruling::Split(vec![
+    Allocation {
+      choice: 1,
+      share: Decimal::percent(50),
+    },
+    Allocation {
+      choice: 0,
+      share: Decimal::percent(50),
+    },
+  ]);
+  suite.decide("dave", split.clone()).unwrap();
+  suite.decide("erin", split).unwrap();
+  assert_eq!(suite.trial().status, Status::HasVerdict);
+
+  // after juror fees of 70, each choice is allocated 315 of the pot of 700
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), BALANCE - 400 + 315 + 157);
+  assert!(suite.claim("alice").is_err());
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE - 200 + 157);
+  assert!(suite.claim("carol").is_err());
+  assert_eq!(suite.stats().claimants_remaining, 0);
+}
+
+#[test]
+fn ranked_claims_follow_the_payout_table() {
+  let mut suite = Suite::with(|_, msg| msg.verdict.payout_table = Some(vec![70, 30]));
+  suite.vote("alice", 2, 1);
+  suite.vote("bob", 0, 1);
+  suite.vote("carol", 1, 1);
+  suite.close_voting();
+
+  // rankings are required in ranked trials
+  assert!(suite.decide("dave", Ruling::Choice(2)).is_err());
+  suite.decide("dave", Ruling::Ranking(vec![2, 0])).unwrap();
+  suite.decide("erin", Ruling::Ranking(vec![2, 0])).unwrap();
+  assert_eq!(suite.trial().winner, Some(2));
+
+  // after juror fees of 30, first place takes 70% of 270 and second place 30%
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.balance("alice"), BALANCE - 100 + 189);
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("bob"), BALANCE - 100 + 81);
+  assert!(suite.claim("carol").is_err());
+}
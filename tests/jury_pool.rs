@@ -0,0 +1,153 @@
+mod common;
+
+use common::{juror_params, Suite, BALANCE, VOTING_SECONDS};
+use cosmwasm_std::{Binary, Timestamp};
+use cw_contract_template::{
+  models::{Drand, Status},
+  msg::{ExecuteMsg, JuryPool},
+};
+
+/// The League of Entropy mainnet, which runs the chained scheme.
+const DRAND_PUBKEY: &str = "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31";
+const DRAND_GENESIS: u64 = 1595431050;
+const ROUND: u64 = 72785;
+const PREVIOUS_SIGNATURE: &str = "a609e19a03c2fcc559e8dae14900aaefe517cb55c840f6e69bc8e4f66c8d18e8a609685d9917efbfb0c37f058c2de88f13d297c7e19e0ab24813079efe57a182554ff054c7638153f9b26a60e7111f71a0ff63d9571704905d3ca6df0b031747";
+const SIGNATURE: &str = "82f5d3d2de4db19d40a6980e8aa37842a0e55d1df06bd68bddc8d60002e8e959eb9cfa368b3c1b77d18f02a54fe047b80f0989315f83b12a74fd8679c4f12aae86eaf6ab5690b34f1fddd50ee3cc6f6cdf59e95526d5a5d82aaa84fa6f181e42";
+const CANDIDATES: [&str; 5] = ["dave", "erin", "frank", "grace", "heidi"];
+
+/// A panel of 3 drawn from 5 candidates by the League of Entropy mainnet.
+fn jury_pool() -> JuryPool {
+  JuryPool {
+    candidates: CANDIDATES.iter().map(|name| juror_params(name)).collect(),
+    panel_size: 3,
+    drand: Drand {
+      pubkey: Binary(hex::decode(DRAND_PUBKEY).unwrap()),
+      genesis_time: Timestamp::from_seconds(DRAND_GENESIS),
+      period_seconds: 30,
+    },
+  }
+}
+
+/// A trial with the jury pool, where `ROUND` is the first round published
+/// once voting closes.
+fn pool_suite() -> Suite {
+  Suite::draft(|app, msg| {
+    let start = Timestamp::from_seconds(DRAND_GENESIS + 30 * (ROUND - 1));
+    app.update_block(|block| block.time = start.minus_seconds(VOTING_SECONDS));
+    msg.verdict.start = start;
+    msg.jury = vec![];
+    msg.jury_pool = Some(jury_pool());
+  })
+}
+
+fn beacon(signature: &str) -> ExecuteMsg {
+  ExecuteMsg::SubmitBeacon {
+    round: ROUND,
+    signature: Binary(hex::decode(signature).unwrap()),
+    previous_signature: Binary(hex::decode(PREVIOUS_SIGNATURE).unwrap()),
+  }
+}
+
+/// Submit the beacon, returning the panel it selected.
+fn draw_panel(suite: &mut Suite) -> Vec<String> {
+  let resp = suite.execute("alice", &beacon(SIGNATURE), &[]).unwrap();
+  resp
+    .events
+    .iter()
+    .find(|event| event.ty == "wasm")
+    .and_then(|event| event.attributes.iter().find(|attr| attr.key == "panel"))
+    .unwrap()
+    .value
+    .split(',')
+    .map(|juror| juror.to_owned())
+    .collect()
+}
+
+#[test]
+fn beacon_from_drand_mainnet_selects_panel() {
+  let mut suite = pool_suite();
+  suite.publish().unwrap();
+  suite.vote("alice", 0, 1);
+  suite.close_voting();
+
+  // a beacon whose signature doesn't cover the round is rejected
+  assert!(suite
+    .execute("alice", &beacon(PREVIOUS_SIGNATURE), &[])
+    .is_err());
+
+  let panel = draw_panel(&mut suite);
+  assert_eq!(panel.len(), 3);
+  for (i, juror) in panel.iter().enumerate() {
+    assert!(CANDIDATES.contains(&juror.as_str()));
+    assert!(!panel[..i].contains(juror));
+  }
+  assert_eq!(suite.trial().status, Status::Deliberating);
+  assert!(suite.execute("alice", &beacon(SIGNATURE), &[]).is_err());
+}
+
+#[test]
+fn panel_draw_is_deterministic() {
+  let panels: Vec<Vec<String>> = (0..2)
+    .map(|_| {
+      let mut suite = pool_suite();
+      suite.publish().unwrap();
+      suite.close_voting();
+      draw_panel(&mut suite)
+    })
+    .collect();
+  assert_eq!(panels[0], panels[1]);
+  // the draw can be reproduced off-chain from the beacon alone
+  assert_eq!(panels[0], vec!["heidi", "dave", "grace"]);
+}
+
+#[test]
+fn candidates_are_validated_on_publish() {
+  let mut suite = pool_suite();
+  let configure = |suite: &mut Suite, jury_pool| {
+    suite
+      .execute(
+        "owner",
+        &ExecuteMsg::ConfigureJury {
+          jury: vec![],
+          jury_pool: Some(jury_pool),
+        },
+        &[],
+      )
+      .unwrap();
+  };
+
+  // a candidate's public key is neither a compressed nor an uncompressed
+  // secp256k1 key
+  let mut invalid = jury_pool();
+  invalid.candidates[4].pubkey = Some(Binary(vec![2; 32]));
+  configure(&mut suite, invalid);
+  assert!(suite.publish().is_err());
+
+  // a candidate's address isn't valid
+  let mut invalid = jury_pool();
+  invalid.candidates[4] = juror_params("Heidi");
+  configure(&mut suite, invalid);
+  assert!(suite.publish().is_err());
+
+  let mut valid = jury_pool();
+  valid.candidates[4].pubkey = Some(Binary(vec![2; 33]));
+  configure(&mut suite, valid);
+  suite.publish().unwrap();
+}
+
+#[test]
+fn trial_without_a_panel_hangs_once_deliberations_expire() {
+  let mut suite = pool_suite();
+  suite.publish().unwrap();
+  suite.vote("alice", 0, 1);
+  suite.close_voting();
+
+  // no beacon was submitted, so there are no jurors to co-sign
+  assert!(suite.cancel("no panel drawn").is_err());
+  suite.expire_deliberations();
+
+  assert!(suite.execute("alice", &beacon(SIGNATURE), &[]).is_err());
+  suite.claim("alice").unwrap();
+  assert_eq!(suite.trial().status, Status::HungJury);
+  assert_eq!(suite.balance("alice"), BALANCE);
+}
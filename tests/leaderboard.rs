@@ -0,0 +1,94 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::{Addr, Uint128};
+use cw_contract_template::{
+  models::Ruling,
+  msg::{GetLeaderboardResponse, LeaderboardEntry, QueryMsg},
+};
+
+fn leaderboard(
+  suite: &Suite,
+  choice: Option<u32>,
+  limit: Option<u32>,
+) -> Vec<LeaderboardEntry> {
+  suite
+    .query::<GetLeaderboardResponse>(&QueryMsg::GetLeaderboard { choice, limit })
+    .entries
+}
+
+fn entry(
+  address: &str,
+  choice: u32,
+  weight: u32,
+  payout: Option<u128>,
+) -> LeaderboardEntry {
+  LeaderboardEntry {
+    address: Addr::unchecked(address),
+    choice,
+    weight,
+    payout: payout.map(Uint128::from),
+  }
+}
+
+#[test]
+fn leaderboard_ranks_by_weight() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 1);
+  suite.vote("bob", 0, 3);
+  suite.vote("carol", 1, 5);
+
+  // before the verdict, positions are ranked by weight
+  assert_eq!(
+    leaderboard(&suite, None, None),
+    vec![
+      entry("carol", 1, 5, None),
+      entry("bob", 0, 3, None),
+      entry("alice", 0, 1, None),
+    ]
+  );
+  assert_eq!(
+    leaderboard(&suite, Some(0), None),
+    vec![entry("bob", 0, 3, None), entry("alice", 0, 1, None)]
+  );
+  assert_eq!(
+    leaderboard(&suite, None, Some(1)),
+    vec![entry("carol", 1, 5, None)]
+  );
+
+  // a voter adding to a position moves it up the board
+  suite.vote("alice", 0, 4);
+  assert_eq!(
+    leaderboard(&suite, Some(0), None),
+    vec![entry("alice", 0, 5, None), entry("bob", 0, 3, None)]
+  );
+}
+
+#[test]
+fn winners_are_ranked_by_payout() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 1);
+  suite.vote("bob", 0, 3);
+  suite.vote("carol", 1, 5);
+
+  // once the verdict is in, the winners are ranked by their share of the 810
+  // left after juror fees
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  suite.decide("erin", Ruling::Choice(0)).unwrap();
+  assert_eq!(
+    leaderboard(&suite, None, None),
+    vec![
+      entry("bob", 0, 3, Some(607)),
+      entry("alice", 0, 1, Some(202))
+    ]
+  );
+  assert_eq!(
+    leaderboard(&suite, None, Some(1)),
+    vec![entry("bob", 0, 3, Some(607))]
+  );
+  assert_eq!(
+    leaderboard(&suite, Some(1), None),
+    vec![entry("carol", 1, 5, Some(0))]
+  );
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::{decide_msg, logs, Suite};
+use cw_contract_template::models::{Logs, Ruling, Status};
+
+/// A trial whose voting has closed, with a single vote.
+fn deliberating_suite() -> Suite {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 1);
+  suite.close_voting();
+  suite
+}
+
+#[test]
+fn logs_must_be_well_formed() {
+  let mut suite = deliberating_suite();
+  let invalid = vec![
+    // not a sha256 digest
+    Logs {
+      hash: "ok".to_owned(),
+      ..logs("ok")
+    },
+    // neither matching the inline logs' length nor their hash
+    Logs {
+      len: 3,
+      ..logs("ok")
+    },
+    Logs {
+      hash: logs("ko").hash,
+      ..logs("ok")
+    },
+    // neither inline nor referenced by uri
+    Logs {
+      inline: None,
+      ..logs("ok")
+    },
+  ];
+  for logs in invalid {
+    let err = suite
+      .execute("dave", &decide_msg(Ruling::Choice(1), logs), &[])
+      .unwrap_err();
+    assert_eq!(err.root_cause().to_string(), "InvalidLogs");
+  }
+
+  let uploaded = Logs {
+    inline: None,
+    uri: Some("ipfs://logs".to_owned()),
+    ..logs("ok")
+  };
+  suite
+    .execute("dave", &decide_msg(Ruling::Choice(1), uploaded), &[])
+    .unwrap();
+}
+
+#[test]
+fn inline_logs_are_limited_in_size() {
+  let mut suite = Suite::with(|_, msg| msg.verdict.max_inline_logs = Some(4));
+  suite.vote("alice", 1, 1);
+  suite.close_voting();
+
+  let err = suite
+    .execute("dave", &decide_msg(Ruling::Choice(1), logs("hello")), &[])
+    .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "LogsTooLarge");
+  suite
+    .execute("dave", &decide_msg(Ruling::Choice(1), logs("hell")), &[])
+    .unwrap();
+}
+
+#[test]
+fn jury_hangs_when_logs_differ() {
+  let mut suite = deliberating_suite();
+  suite
+    .execute("dave", &decide_msg(Ruling::Choice(1), logs("ok")), &[])
+    .unwrap();
+  suite
+    .execute("erin", &decide_msg(Ruling::Choice(1), logs("ok\n")), &[])
+    .unwrap();
+  assert_eq!(suite.trial().status, Status::HungJury);
+
+  suite.claim("alice").unwrap();
+}
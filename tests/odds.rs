@@ -0,0 +1,76 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Decimal;
+use cw_contract_template::{
+  models::OddsSnapshot,
+  msg::{GetOddsHistoryResponse, GetOddsResponse, QueryMsg},
+};
+
+fn odds_history(
+  suite: &Suite,
+  start_after: Option<u64>,
+  limit: Option<u32>,
+) -> Vec<OddsSnapshot> {
+  suite
+    .query::<GetOddsHistoryResponse>(&QueryMsg::GetOddsHistory { start_after, limit })
+    .snapshots
+}
+
+#[test]
+fn odds_are_snapshot_once_per_interval() {
+  let mut suite = Suite::new();
+  let now = suite.app.block_info().time.seconds();
+  let bucket = now - now % 600;
+
+  // votes within the same 10 minute interval share a snapshot
+  suite.vote("alice", 0, 1);
+  suite.advance(60);
+  suite.vote("bob", 1, 1);
+  let last_vote = suite.app.block_info().time;
+  suite.advance(600);
+  suite.vote("carol", 2, 2);
+  // intervals without votes are skipped
+  suite.advance(1200);
+  suite.vote("alice", 1, 1);
+
+  let snapshots = odds_history(&suite, None, None);
+  let buckets: Vec<u64> = snapshots.iter().map(|snapshot| snapshot.bucket).collect();
+  assert_eq!(buckets, vec![bucket, bucket + 600, bucket + 1800]);
+  assert_eq!(snapshots[0].time, last_vote);
+  assert_eq!(snapshots[0].weight, 2);
+  assert_eq!(snapshots[0].weights, vec![1, 1, 0]);
+  assert_eq!(snapshots[1].weights, vec![1, 1, 2]);
+  assert_eq!(snapshots[2].weight, 5);
+  assert_eq!(snapshots[2].weights, vec![1, 2, 2]);
+
+  // pages follow on from the last bucket seen
+  let page = odds_history(&suite, None, Some(2));
+  assert_eq!(page, snapshots[..2]);
+  let page = odds_history(&suite, Some(page[1].bucket), Some(2));
+  assert_eq!(page, snapshots[2..]);
+}
+
+#[test]
+fn odds_pay_first_place_its_share_of_the_payout_table() {
+  let shares = |suite: &Suite| -> Vec<Decimal> {
+    suite
+      .query::<GetOddsResponse>(&QueryMsg::GetOdds {})
+      .choices
+      .iter()
+      .map(|choice| choice.share)
+      .collect()
+  };
+
+  // a draft's payout table isn't validated until it's published
+  let suite = Suite::draft(|_, msg| msg.verdict.payout_table = Some(vec![]));
+  assert_eq!(shares(&suite), vec![Decimal::zero(); 3]);
+
+  let mut suite = Suite::with(|_, msg| msg.verdict.payout_table = Some(vec![70, 30]));
+  assert_eq!(shares(&suite), vec![Decimal::percent(70); 3]);
+  suite.vote("alice", 0, 1);
+  let odds: GetOddsResponse = suite.query(&QueryMsg::GetOdds {});
+  // after juror fees of 10%, first place would take 70% of 90
+  assert_eq!(odds.choices[0].multiplier, Some(Decimal::percent(63)));
+  assert_eq!(odds.choices[1].multiplier, None);
+}
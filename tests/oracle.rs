@@ -0,0 +1,180 @@
+mod common;
+
+use common::{use_oracle, Suite};
+use cosmwasm_std::{Decimal, Uint128};
+use cw_contract_template::{
+  models::{Ruling, Scalar, ScalarRange, Status},
+  msg::{GetStatsResponse, QueryMsg},
+};
+use std::str::FromStr;
+
+#[test]
+fn oracle_value_maps_to_winning_choice() {
+  let mut suite = Suite::with_oracle("12.5");
+  suite.vote("alice", 1, 3);
+  suite.vote("bob", 1, 1);
+  suite.vote("carol", 2, 4);
+  suite.close_voting();
+
+  suite.resolve().unwrap();
+
+  let trial = suite.trial();
+  assert_eq!(trial.status, Status::HasVerdict);
+  assert_eq!(trial.winner, Some(1));
+
+  // winners split the whole pot in proportion to their weight
+  suite.claim("alice").unwrap();
+  suite.claim("bob").unwrap();
+  assert_eq!(suite.balance("alice"), 10_000 - 300 + 600);
+  assert_eq!(suite.balance("bob"), 10_000 - 100 + 200);
+  assert!(suite.claim("carol").is_err());
+
+  let stats: GetStatsResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetStats {})
+    .unwrap();
+  assert_eq!(stats.stats.total_claimed, Uint128::from(800u128));
+  assert_eq!(stats.stats.liabilities, Uint128::zero());
+}
+
+#[test]
+fn oracle_rules_include_lower_bound() {
+  let mut suite = Suite::with_oracle("20");
+  suite.vote("alice", 2, 1);
+  suite.close_voting();
+
+  suite.resolve().unwrap();
+  assert_eq!(suite.trial().winner, Some(2));
+}
+
+/// Scalar ranges from pairs of decimal bounds.
+fn ranges(bounds: &[(&str, &str)]) -> Vec<ScalarRange> {
+  bounds
+    .iter()
+    .map(|(min, max)| ScalarRange {
+      min: Decimal::from_str(min).unwrap(),
+      max: Decimal::from_str(max).unwrap(),
+    })
+    .collect()
+}
+
+#[test]
+fn linear_scalar_value_splits_pot_between_buckets() {
+  let ranges = ranges(&[("0", "10"), ("10", "20"), ("20", "30")]);
+  // 17.5 lies a quarter of the way from the "mid" midpoint to the "high" one
+  let mut suite = Suite::with(|app, msg| {
+    use_oracle(app, msg, "17.5");
+    msg.verdict.scalar = Some(Scalar {
+      ranges,
+      linear: true,
+    });
+  });
+  suite.vote("alice", 1, 2);
+  suite.vote("bob", 2, 1);
+  suite.vote("carol", 0, 1);
+  suite.close_voting();
+
+  suite.resolve().unwrap();
+  assert_eq!(suite.trial().winner, Some(1));
+
+  suite.claim("alice").unwrap();
+  suite.claim("bob").unwrap();
+  assert!(suite.claim("carol").is_err());
+  assert_eq!(suite.balance("alice"), 10_000 - 200 + 300);
+  assert_eq!(suite.balance("bob"), 10_000 - 100 + 100);
+}
+
+#[test]
+fn scalar_ranges_must_be_contiguous_and_cover_each_choice() {
+  let invalid = [
+    // a gap between "low" and "mid"
+    ranges(&[("0", "10"), ("11", "20"), ("20", "30")]),
+    // "low" has its bounds reversed
+    ranges(&[("10", "0"), ("0", "20"), ("20", "30")]),
+    // "mid" is empty
+    ranges(&[("0", "10"), ("10", "10"), ("10", "30")]),
+    // one range too few, and one too many
+    ranges(&[("0", "10"), ("10", "20")]),
+    ranges(&[("0", "10"), ("10", "20"), ("20", "30"), ("30", "40")]),
+  ];
+  for invalid_ranges in invalid {
+    let mut suite = Suite::draft(|_, msg| {
+      msg.verdict.scalar = Some(Scalar {
+        ranges: invalid_ranges.clone(),
+        linear: false,
+      })
+    });
+    assert!(suite.publish().is_err(), "{:?} is invalid", invalid_ranges);
+  }
+
+  let mut suite = Suite::draft(|_, msg| {
+    msg.verdict.scalar = Some(Scalar {
+      ranges: ranges(&[("0", "10"), ("10", "20"), ("20", "30")]),
+      linear: false,
+    })
+  });
+  suite.publish().unwrap();
+}
+
+#[test]
+fn decided_value_maps_to_the_range_containing_it() {
+  // ranges include their lower bound, and values outside of them are clamped
+  let cases = [
+    ("0", 0),
+    ("9.999", 0),
+    ("10", 1),
+    ("19.999", 1),
+    ("20", 2),
+    ("30", 2),
+    ("45", 2),
+  ];
+  for (value, winner) in cases {
+    let mut suite = Suite::with(|_, msg| {
+      msg.verdict.scalar = Some(Scalar {
+        ranges: ranges(&[("0", "10"), ("10", "20"), ("20", "30")]),
+        linear: false,
+      })
+    });
+    suite.vote("alice", winner, 1);
+    suite.close_voting();
+
+    let ruling = Ruling::Value(Decimal::from_str(value).unwrap());
+    suite.decide("dave", ruling.clone()).unwrap();
+    suite.decide("erin", ruling).unwrap();
+    let trial = suite.trial();
+    assert_eq!(trial.status, Status::HasVerdict);
+    assert_eq!(trial.winner, Some(winner), "{} maps to {}", value, winner);
+  }
+}
+
+#[test]
+fn cannot_resolve_while_voting_is_open() {
+  let mut suite = Suite::with_oracle("5");
+  suite.vote("alice", 0, 1);
+
+  assert!(suite.resolve().is_err());
+  assert_eq!(suite.trial().status, Status::Active);
+}
+
+#[test]
+fn cannot_resolve_after_deliberations_expire() {
+  let mut suite = Suite::with_oracle("5");
+  suite.vote("alice", 0, 1);
+  suite.close_voting();
+  suite
+    .app
+    .update_block(|block| block.time = block.time.plus_seconds(3601));
+
+  assert!(suite.resolve().is_err());
+}
+
+#[test]
+fn cannot_resolve_twice() {
+  let mut suite = Suite::with_oracle("5");
+  suite.vote("alice", 0, 1);
+  suite.close_voting();
+
+  suite.resolve().unwrap();
+  assert!(suite.resolve().is_err());
+}
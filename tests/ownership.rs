@@ -0,0 +1,63 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Addr;
+use cw_contract_template::msg::{ExecuteMsg, GetOwnershipResponse, QueryMsg};
+use cw_multi_test::Executor;
+
+#[test]
+fn ownership_is_transferred_once_accepted() {
+  let mut suite = Suite::with_oracle("12.5");
+  let owner = suite.owner.clone();
+  let new_owner = Addr::unchecked("new_owner");
+  let cancel = ExecuteMsg::Cancel {
+    reason: "feed halted".to_owned(),
+  };
+  suite
+    .app
+    .execute_contract(
+      owner.clone(),
+      suite.trial.clone(),
+      &ExecuteMsg::ProposeOwner {
+        new_owner: new_owner.to_string(),
+        expiry: None,
+      },
+      &[],
+    )
+    .unwrap();
+  assert!(suite
+    .app
+    .execute_contract(
+      Addr::unchecked("alice"),
+      suite.trial.clone(),
+      &ExecuteMsg::AcceptOwnership {},
+      &[],
+    )
+    .is_err());
+  suite
+    .app
+    .execute_contract(
+      new_owner.clone(),
+      suite.trial.clone(),
+      &ExecuteMsg::AcceptOwnership {},
+      &[],
+    )
+    .unwrap();
+
+  let ownership: GetOwnershipResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetOwnership {})
+    .unwrap();
+  assert_eq!(ownership.owner, Some(new_owner.clone()));
+  assert_eq!(ownership.pending_owner, None);
+
+  assert!(suite
+    .app
+    .execute_contract(owner, suite.trial.clone(), &cancel, &[])
+    .is_err());
+  suite
+    .app
+    .execute_contract(new_owner, suite.trial.clone(), &cancel, &[])
+    .unwrap();
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Addr;
+use cw_contract_template::{
+  models::PausableOperation,
+  msg::{ExecuteMsg, GetPauseStateResponse, QueryMsg},
+};
+use cw_multi_test::Executor;
+
+#[test]
+fn guardian_can_pause_claims_for_a_bounded_time() {
+  let mut suite = Suite::with_oracle("12.5");
+  let guardian = Addr::unchecked("guardian");
+  suite.vote("alice", 1, 3);
+  suite.close_voting();
+  suite.resolve().unwrap();
+
+  // only the owner can appoint a guardian
+  let set_guardian = ExecuteMsg::SetGuardian {
+    guardian: Some(guardian.to_string()),
+  };
+  assert!(suite
+    .app
+    .execute_contract(guardian.clone(), suite.trial.clone(), &set_guardian, &[])
+    .is_err());
+  suite
+    .app
+    .execute_contract(suite.owner.clone(), suite.trial.clone(), &set_guardian, &[])
+    .unwrap();
+
+  let pause = |seconds: u64| ExecuteMsg::Pause {
+    operation: PausableOperation::Claim,
+    seconds,
+  };
+  // pauses can't exceed the limit in total
+  assert!(suite
+    .app
+    .execute_contract(
+      guardian.clone(),
+      suite.trial.clone(),
+      &pause(7 * 24 * 60 * 60 + 1),
+      &[],
+    )
+    .is_err());
+  suite
+    .app
+    .execute_contract(guardian.clone(), suite.trial.clone(), &pause(3600), &[])
+    .unwrap();
+
+  let state: GetPauseStateResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetPauseState {})
+    .unwrap();
+  let claim_state = state
+    .operations
+    .iter()
+    .find(|state| state.operation == PausableOperation::Claim)
+    .unwrap();
+  assert!(claim_state.paused);
+  assert_eq!(claim_state.seconds_used, 3600);
+
+  let err = suite.claim("alice").unwrap_err();
+  assert!(err.root_cause().to_string().contains("claim is paused"));
+
+  // unpausing early refunds the rest of the pause
+  suite.app.update_block(|block| {
+    block.time = block.time.plus_seconds(600);
+  });
+  suite
+    .app
+    .execute_contract(
+      guardian,
+      suite.trial.clone(),
+      &ExecuteMsg::Unpause {
+        operation: PausableOperation::Claim,
+      },
+      &[],
+    )
+    .unwrap();
+  let state: GetPauseStateResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(suite.trial.clone(), &QueryMsg::GetPauseState {})
+    .unwrap();
+  assert!(state.operations.iter().all(|state| !state.paused));
+  assert_eq!(state.operations[1].seconds_used, 600);
+
+  suite.claim("alice").unwrap();
+}
+
+#[test]
+fn pausing_decisions_extends_deliberations() {
+  let mut suite = Suite::with_oracle("12.5");
+  suite.vote("alice", 1, 1);
+  suite.close_voting();
+  suite.advance(3000);
+
+  let pause = ExecuteMsg::Pause {
+    operation: PausableOperation::Decide,
+    seconds: 1800,
+  };
+  suite.execute("owner", &pause, &[]).unwrap();
+  assert!(suite.resolve().is_err());
+
+  // the deliberation period would have ended 600 seconds into the pause
+  suite.advance(1800);
+  suite.resolve().unwrap();
+  assert_eq!(suite.trial().winner, Some(1));
+}
+
+#[test]
+fn decisions_cannot_be_paused_once_deliberations_expire() {
+  let mut suite = Suite::with_oracle("12.5");
+  suite.vote("alice", 1, 1);
+  suite.close_voting();
+  suite.expire_deliberations();
+
+  let pause = ExecuteMsg::Pause {
+    operation: PausableOperation::Decide,
+    seconds: 3600,
+  };
+  assert!(suite.execute("owner", &pause, &[]).is_err());
+  assert!(suite.resolve().is_err());
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::{to_vec, Addr, Binary, Timestamp};
+use cw_contract_template::{
+  models::{Provider, ProviderResult, Ruling, Status},
+  msg::ExecuteMsg,
+};
+use cw_multi_test::AppResponse;
+use ed25519_zebra::{SigningKey, VerificationKey};
+
+fn signing_key(seed: u8) -> SigningKey {
+  SigningKey::from([seed; 32])
+}
+
+/// The default trial, resolved by a provider signing with the key seeded by 1
+/// rather than by a jury.
+fn provider_suite() -> Suite {
+  Suite::with(|_, msg| {
+    msg.jury = vec![];
+    msg.verdict.provider = Some(Provider {
+      pubkey: Binary(<[u8; 32]>::from(VerificationKey::from(&signing_key(1))).to_vec()),
+    });
+  })
+}
+
+/// Relay a result for `trial`, produced `seconds` after voting closed and
+/// signed with the key seeded by `seed`.
+fn submit(
+  suite: &mut Suite,
+  seed: u8,
+  trial: &Addr,
+  seconds: u64,
+  ruling: Ruling,
+) -> anyhow::Result<AppResponse> {
+  let start = suite.trial().verdict.start;
+  let payload = to_vec(&ProviderResult {
+    trial: trial.clone(),
+    timestamp: Timestamp::from_seconds(start.seconds() + seconds),
+    ruling,
+  })
+  .unwrap();
+  let signature = signing_key(seed).sign(&payload);
+  suite.execute(
+    "relayer",
+    &ExecuteMsg::SubmitSignedResult {
+      payload: Binary(payload),
+      signature: Binary(<[u8; 64]>::from(signature).to_vec()),
+    },
+    &[],
+  )
+}
+
+#[test]
+fn signed_result_decides_the_trial() {
+  let mut suite = provider_suite();
+  suite.vote("alice", 2, 1);
+  suite.close_voting();
+
+  let trial = suite.trial.clone();
+  submit(&mut suite, 1, &trial, 60, Ruling::Choice(2)).unwrap();
+  let trial = suite.trial();
+  assert_eq!(trial.status, Status::HasVerdict);
+  assert_eq!(trial.winner, Some(2));
+}
+
+#[test]
+fn invalid_results_are_rejected() {
+  let mut suite = provider_suite();
+  suite.vote("alice", 2, 1);
+  suite.close_voting();
+  let trial = suite.trial.clone();
+
+  // signed by someone other than the provider
+  let err = submit(&mut suite, 2, &trial, 60, Ruling::Choice(2)).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "InvalidSignature");
+
+  // meant for another trial
+  let other = Addr::unchecked("other");
+  let err = submit(&mut suite, 1, &other, 60, Ruling::Choice(2)).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "InvalidPayload");
+
+  // produced no later than voting closed
+  let err = submit(&mut suite, 1, &trial, 0, Ruling::Choice(2)).unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "InvalidPayload");
+
+  assert_eq!(suite.trial().status, Status::Active);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::Suite;
+use cw_contract_template::{
+  models::{Ruling, Status},
+  msg::{GetResolutionResponse, QueryMsg},
+};
+
+fn resolution(suite: &Suite) -> GetResolutionResponse {
+  suite.query(&QueryMsg::GetResolution {})
+}
+
+#[test]
+fn trial_is_unresolved_until_it_has_an_outcome() {
+  let mut suite = Suite::draft(|_, _| {});
+  let resp = resolution(&suite);
+  assert!(!resp.resolved);
+  assert_eq!(resp.status, Status::Draft);
+  assert_eq!(resp.winner, None);
+  assert_eq!(resp.resolved_at, None);
+
+  suite.publish().unwrap();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(1)).unwrap();
+  let resp = resolution(&suite);
+  assert!(!resp.resolved);
+  assert_eq!(resp.status, Status::Deliberating);
+  assert_eq!(resp.winner, None);
+  assert_eq!(resp.resolved_at, None);
+}
+
+#[test]
+fn verdict_resolves_the_trial() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(1)).unwrap();
+  suite.advance(60);
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+
+  let resp = resolution(&suite);
+  assert!(resp.resolved);
+  assert_eq!(resp.status, Status::HasVerdict);
+  assert_eq!(resp.winner, Some(1));
+  assert_eq!(resp.resolved_at, Some(suite.app.block_info().time));
+}
+
+#[test]
+fn cancellation_resolves_the_trial() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 2);
+  suite.advance(60);
+  suite.cancel("feed halted").unwrap();
+
+  let resp = resolution(&suite);
+  assert!(resp.resolved);
+  assert_eq!(resp.status, Status::Dismissed);
+  assert_eq!(resp.winner, None);
+  assert_eq!(resp.resolved_at, Some(suite.app.block_info().time));
+}
+
+#[test]
+fn hung_jury_resolves_the_trial() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 2);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  suite.advance(60);
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+
+  let resp = resolution(&suite);
+  assert!(resp.resolved);
+  assert_eq!(resp.status, Status::HungJury);
+  assert_eq!(resp.winner, None);
+  assert_eq!(resp.resolved_at, Some(suite.app.block_info().time));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::{logs, Suite, SCRIPT_HASH};
+use cw_contract_template::{
+  models::{EnvVar, OutputFormat, Ruling, Runtime, Status},
+  msg::ExecuteMsg,
+};
+
+#[test]
+fn script_body_must_match_its_hash() {
+  let mut suite = Suite::draft(|_, msg| msg.verdict.script.body = Some("echo 1".to_owned()));
+  assert!(suite.publish().is_err());
+
+  let mut suite = Suite::draft(|_, msg| {
+    msg.verdict.script.hash = SCRIPT_HASH.to_uppercase();
+    msg.verdict.script.body = None;
+  });
+  assert!(suite.publish().is_err());
+
+  let mut suite = Suite::draft(|_, msg| msg.verdict.script.body = None);
+  suite.publish().unwrap();
+}
+
+#[test]
+fn jurors_must_echo_the_script_hash() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 1, 1);
+  suite.close_voting();
+
+  let err = suite
+    .execute(
+      "dave",
+      &ExecuteMsg::Decide {
+        choice: 1,
+        logs: logs("ok"),
+        script_hash: "0".repeat(64),
+      },
+      &[],
+    )
+    .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "ScriptHashMismatch");
+
+  suite.decide("dave", Ruling::Choice(1)).unwrap();
+  suite.decide("erin", Ruling::Choice(1)).unwrap();
+  assert_eq!(suite.trial().status, Status::HasVerdict);
+}
+
+fn runtime() -> Runtime {
+  Runtime {
+    version: "5.1.16".to_owned(),
+    lockfile_hash: Some(SCRIPT_HASH.to_owned()),
+    args: vec!["--verbose".to_owned()],
+    env: vec![EnvVar {
+      name: "TZ".to_owned(),
+      value: "UTC".to_owned(),
+    }],
+    output: OutputFormat::Choice,
+  }
+}
+
+#[test]
+fn runtime_is_validated_on_publish() {
+  let var = |name: &str| EnvVar {
+    name: name.to_owned(),
+    value: String::new(),
+  };
+  let invalid = vec![
+    Runtime {
+      version: String::new(),
+      ..runtime()
+    },
+    Runtime {
+      version: "5.1 beta".to_owned(),
+      ..runtime()
+    },
+    Runtime {
+      lockfile_hash: Some("lockfile".to_owned()),
+      ..runtime()
+    },
+    Runtime {
+      args: vec![String::new(); 33],
+      ..runtime()
+    },
+    Runtime {
+      args: vec!["a".repeat(257)],
+      ..runtime()
+    },
+    Runtime {
+      env: (0..33).map(|i| var(&format!("V{}", i))).collect(),
+      ..runtime()
+    },
+    Runtime {
+      env: vec![var("1ST")],
+      ..runtime()
+    },
+    Runtime {
+      env: vec![var("A-B")],
+      ..runtime()
+    },
+    Runtime {
+      env: vec![var("TZ"), var("TZ")],
+      ..runtime()
+    },
+    // the trial is neither scalar nor ranked
+    Runtime {
+      output: OutputFormat::Value,
+      ..runtime()
+    },
+    Runtime {
+      output: OutputFormat::Ranking,
+      ..runtime()
+    },
+  ];
+  for runtime in invalid {
+    let mut suite = Suite::draft(|_, msg| msg.verdict.runtime = Some(runtime.clone()));
+    assert!(suite.publish().is_err(), "{:?} is invalid", runtime);
+  }
+
+  let mut suite = Suite::draft(|_, msg| {
+    msg.verdict.runtime = Some(Runtime {
+      output: OutputFormat::Allocations,
+      ..runtime()
+    })
+  });
+  suite.publish().unwrap();
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::Uint128;
+use cw_contract_template::models::{Ruling, Stats};
+
+#[test]
+fn stats_track_votes_refunds_and_claims() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 2);
+  suite.vote("alice", 1, 1);
+  suite.vote("bob", 1, 1);
+  assert_eq!(
+    suite.stats(),
+    Stats {
+      voter_count: 2,
+      vote_count: 3,
+      total_escrowed: Uint128::from(400u128),
+      ..Stats::default()
+    }
+  );
+
+  // once the trial is canceled, every voter is owed a refund
+  suite.cancel("feed halted").unwrap();
+  let stats = suite.stats();
+  assert_eq!(stats.liabilities, Uint128::from(400u128));
+  assert_eq!(stats.claimants_remaining, 2);
+  assert_eq!(stats.juror_fees_paid, Uint128::zero());
+
+  suite.claim("alice").unwrap();
+  let stats = suite.stats();
+  assert_eq!(stats.total_claimed, Uint128::from(300u128));
+  assert_eq!(stats.liabilities, Uint128::from(100u128));
+  assert_eq!(stats.claimants_remaining, 1);
+}
+
+#[test]
+fn stats_track_juror_fees_and_rewards() {
+  let mut suite = Suite::new();
+  suite.vote("alice", 0, 3);
+  suite.vote("bob", 1, 1);
+  suite.close_voting();
+  suite.decide("dave", Ruling::Choice(0)).unwrap();
+  suite.decide("erin", Ruling::Choice(0)).unwrap();
+
+  // jurors are paid 5% of the pot of 400 each
+  let stats = suite.stats();
+  assert_eq!(stats.juror_fees_paid, Uint128::from(40u128));
+  assert_eq!(stats.liabilities, Uint128::from(360u128));
+  assert_eq!(stats.claimants_remaining, 1);
+
+  suite.claim("alice").unwrap();
+  let stats = suite.stats();
+  assert_eq!(stats.total_claimed, Uint128::from(360u128));
+  assert_eq!(stats.liabilities, Uint128::zero());
+  assert_eq!(stats.claimants_remaining, 0);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::Suite;
+use cosmwasm_std::{Addr, Event};
+use cw_contract_template::msg::{ExecuteMsg, GetConfigHashResponse, QueryMsg};
+use cw_multi_test::Executor;
+
+#[test]
+fn trial_is_frozen_after_first_vote() {
+  let mut suite = Suite::with_oracle("12.5");
+  let update = ExecuteMsg::UpdateTrial {
+    prompt: Some("Where will JUNO close?".to_owned()),
+    choice_texts: Some(vec![
+      "low".to_owned(),
+      "medium".to_owned(),
+      "high".to_owned(),
+    ]),
+    style: None,
+  };
+  let owner = suite.owner.clone();
+  assert!(suite
+    .app
+    .execute_contract(Addr::unchecked("alice"), suite.trial.clone(), &update, &[])
+    .is_err());
+  suite
+    .app
+    .execute_contract(owner.clone(), suite.trial.clone(), &update, &[])
+    .unwrap();
+  let trial = suite.trial();
+  assert_eq!(trial.prompt, "Where will JUNO close?");
+  assert_eq!(trial.choices[1].text, "medium");
+
+  suite.vote("alice", 1, 1);
+  assert!(suite
+    .app
+    .execute_contract(owner, suite.trial.clone(), &update, &[])
+    .is_err());
+}
+
+#[test]
+fn config_hash_follows_updates_after_publish() {
+  let config_hash = |suite: &Suite| {
+    suite
+      .query::<GetConfigHashResponse>(&QueryMsg::GetConfigHash {})
+      .config_hash
+  };
+  let update = |prompt: &str| ExecuteMsg::UpdateTrial {
+    prompt: Some(prompt.to_owned()),
+    choice_texts: None,
+    style: None,
+  };
+
+  // a draft has no config hash until it's published
+  let mut suite = Suite::draft(|_, _| {});
+  suite
+    .execute("owner", &update("Draft prompt"), &[])
+    .unwrap();
+  assert_eq!(config_hash(&suite), None);
+  let resp = suite.publish().unwrap();
+  let published = config_hash(&suite).unwrap();
+  assert!(resp.has_event(&Event::new("wasm-publish").add_attribute("config_hash", &published)));
+
+  // once published, each update recomputes it
+  let resp = suite
+    .execute("owner", &update("Where will JUNO close?"), &[])
+    .unwrap();
+  let updated = config_hash(&suite).unwrap();
+  assert_ne!(updated, published);
+  assert!(resp.has_event(&Event::new("wasm-update_trial").add_attribute("config_hash", &updated)));
+
+  // and restoring the original prompt restores the original hash
+  suite
+    .execute("owner", &update("Draft prompt"), &[])
+    .unwrap();
+  assert_eq!(config_hash(&suite).unwrap(), published);
+}
@@ -0,0 +1,26 @@
+mod common;
+
+use common::{Suite, DENOM, PRICE, VOTING_SECONDS};
+use cosmwasm_std::coins;
+use cw_contract_template::{models::Status, msg::ExecuteMsg};
+
+#[test]
+fn votes_are_refused_once_voting_closes() {
+  let mut suite = Suite::new();
+  suite.advance(VOTING_SECONDS - 1);
+  suite.vote("alice", 0, 1);
+
+  // the trial is still active until it's moved into deliberations
+  suite.advance(1);
+  let vote = ExecuteMsg::Vote {
+    choice: 0,
+    weight: 1,
+  };
+  let err = suite
+    .execute("bob", &vote, &coins(PRICE, DENOM))
+    .unwrap_err();
+  assert_eq!(err.root_cause().to_string(), "NotActive");
+  let trial = suite.trial();
+  assert_eq!(trial.status, Status::Active);
+  assert_eq!(trial.weight, 1);
+}